@@ -0,0 +1,138 @@
+extern crate howl;
+extern crate getopts;
+
+use std::env;
+use std::collections::BTreeMap;
+
+use howl::game::*;
+use howl::game::frontends::headless::*;
+
+const DEFAULT_NUM_GAMES: usize = 10;
+
+struct GameReport {
+    seed: usize,
+    turns: u64,
+    levels: usize,
+    kills: u64,
+    cause: String,
+}
+
+fn print_usage(program: &str, opts: getopts::Options) {
+    let brief = format!("Usage: {} [options]", program);
+    print!("{}", opts.usage(&brief));
+}
+
+fn play(seed: usize) -> Result<GameReport, String> {
+    // nothing is displayed, so don't keep any frames
+    let renderer = HeadlessKnowledgeRenderer::new_with_recording(GAME_WIDTH, GAME_HEIGHT,
+                                                                 HeadlessRecording::with_max_frames(0));
+
+    // the game is played without menus, so any attempt to read input is a bug
+    let input = ScriptedInputSource::new(Vec::new());
+
+    let mut game = GameCtx::new(renderer, input, seed, GAME_WIDTH, GAME_HEIGHT);
+    game.set_clock(Box::new(InstantClock));
+    let (reason, game_state) = game.play_new_game(BehaviourType::Autoplay)?;
+
+    let stats = game_state.stats();
+
+    let cause = match reason {
+        ExitReason::GameOver(GameOverReason::PlayerDied) => {
+            stats.killed_by.map_or_else(|| "unknown".to_string(), |cause| format!("{:?}", cause))
+        }
+        ExitReason::Quit => "survived".to_string(),
+        ExitReason::Pause => "paused".to_string(),
+    };
+
+    Ok(GameReport {
+        seed: seed,
+        turns: stats.pc_turns,
+        levels: game_state.num_levels(),
+        kills: stats.enemies_killed,
+        cause: cause,
+    })
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let program = args[0].clone();
+
+    let mut opts = getopts::Options::new();
+    opts.optflag("h", "help", "print this help menu");
+    opts.optopt("n", "games", &format!("number of games to play (default {})", DEFAULT_NUM_GAMES), "N");
+    opts.optopt("r", "rngseed", "seed of the first game; subsequent games use consecutive seeds (default 0)", "SEED");
+
+    let matches = match opts.parse(&args[1..]) {
+        Ok(m) => m,
+        Err(f) => {
+            println!("{}", f.to_string());
+            print_usage(&program, opts);
+            return;
+        }
+    };
+
+    if matches.opt_present("h") {
+        print_usage(&program, opts);
+        return;
+    }
+
+    let num_games = match matches.opt_str("games").map(|s| s.parse::<usize>()) {
+        None => DEFAULT_NUM_GAMES,
+        Some(Ok(n)) => n,
+        Some(Err(_)) => {
+            println!("Error: Number of games must be a non-negative integer");
+            return;
+        }
+    };
+
+    let first_seed = match matches.opt_str("rngseed").map(|s| s.parse::<usize>()) {
+        None => 0,
+        Some(Ok(seed)) => seed,
+        Some(Err(_)) => {
+            println!("Error: RNG seed must be a non-negative integer");
+            return;
+        }
+    };
+
+    println!("{:>12} {:>8} {:>8} {:>8}  {}", "seed", "turns", "levels", "kills", "cause");
+
+    let mut reports = Vec::new();
+    for i in 0..num_games {
+        match play(first_seed.wrapping_add(i)) {
+            Ok(report) => {
+                println!("{:>12} {:>8} {:>8} {:>8}  {}",
+                         report.seed, report.turns, report.levels, report.kills, report.cause);
+                reports.push(report);
+            }
+            Err(message) => {
+                println!("Error: {}", message);
+                return;
+            }
+        }
+    }
+
+    if reports.is_empty() {
+        return;
+    }
+
+    let count = reports.len() as f64;
+    let mean_turns = reports.iter().map(|r| r.turns as f64).sum::<f64>() / count;
+    let mean_levels = reports.iter().map(|r| r.levels as f64).sum::<f64>() / count;
+    let mean_kills = reports.iter().map(|r| r.kills as f64).sum::<f64>() / count;
+
+    println!("");
+    println!("games:       {}", reports.len());
+    println!("mean turns:  {:.1}", mean_turns);
+    println!("mean levels: {:.2}", mean_levels);
+    println!("mean kills:  {:.2}", mean_kills);
+
+    let mut causes = BTreeMap::new();
+    for report in reports.iter() {
+        *causes.entry(report.cause.as_str()).or_insert(0) += 1;
+    }
+
+    println!("outcomes:");
+    for (cause, n) in causes.iter() {
+        println!("  {:<16} {}", cause, n);
+    }
+}
@@ -266,6 +266,9 @@ impl Direction {
     pub fn sub_direction(self) -> DirectionType {
         self.profile().direction_type
     }
+    pub fn from_vector(vector: Vector2<isize>) -> Option<Direction> {
+        iter().find(|direction| direction.vector() == vector)
+    }
 }
 
 pub static CARDINAL_DIRECTION_PROFILES:
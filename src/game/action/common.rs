@@ -16,6 +16,7 @@ pub fn open_door(action: &mut EcsAction, door: EntityRef) {
     action.remove_solid(door.id());
     action.insert_opacity(door.id(), 0.0);
     action.insert_door_state(door.id(), DoorState::Open);
+    action.remove_bump_attackable(door.id());
 
     action.insert_tile(door.id(), TileType::OpenDoor);
 
@@ -29,6 +30,7 @@ pub fn close_door(action: &mut EcsAction, door: EntityRef) {
     action.insert_solid(door.id());
     action.insert_opacity(door.id(), 1.0);
     action.insert_door_state(door.id(), DoorState::Closed);
+    action.insert_bump_attackable(door.id());
 
     action.insert_tile(door.id(), TileType::ClosedDoor);
 
@@ -45,15 +47,90 @@ pub fn close(action: &mut EcsAction, entity_id: EntityId, direction: Direction)
 pub fn fire_bullet(action: &mut EcsAction,
                    entity: EntityRef,
                    delta: Coord,
+                   projectile_type: ProjectileType,
                    ids: &EntityIdReserver) {
 
     const SPEED_CELLS_PER_SEC: f64 = 40.0;
 
-    let mut velocity = RealtimeVelocity::new(delta, SPEED_CELLS_PER_SEC);
+    // the aim line is scaled up by this amount before the outer pellets of a
+    // spread shot are offset from it by its perpendicular
+    const SPREAD_SCALE: isize = 4;
+
     let firer_position = entity.position().expect("Entity missing position");
-    let bullet_position = firer_position + velocity.step_in_place();
 
-    prototypes::bullet(action.entity_mut(ids.new_id()), bullet_position, velocity);
+    // entities without a magazine have unlimited ammunition
+    if let Some(mut magazine) = entity.magazine() {
+        if !magazine.fire() {
+            // nothing is fired, so like a failed reload no time passes
+            action.set_no_commit();
+            if entity.contains_pc() {
+                action.set_action_description(ActionDescription {
+                    message: ActionMessageType::PlayerClickEmpty,
+                    coord: firer_position,
+                });
+            }
+            return;
+        }
+        action.insert_magazine(entity.id(), magazine);
+    }
+
+    // remember the choice of projectile for the next shot
+    if entity.contains_projectile_type() {
+        action.insert_projectile_type(entity.id(), projectile_type);
+    }
+
+    let mut velocity = RealtimeVelocity::new(delta, SPEED_CELLS_PER_SEC);
+    let projectile_position = firer_position + velocity.step_in_place();
+
+    match projectile_type {
+        ProjectileType::Bullet => {
+            prototypes::bullet(action.entity_mut(ids.new_id()), projectile_position, velocity);
+        }
+        ProjectileType::Spread => {
+            let perpendicular = Coord::new(-delta.y, delta.x);
+            for offset in -1..2 {
+                let mut velocity = RealtimeVelocity::new(delta * SPREAD_SCALE + perpendicular * offset, SPEED_CELLS_PER_SEC);
+                let pellet_position = firer_position + velocity.step_in_place();
+                prototypes::pellet(action.entity_mut(ids.new_id()), pellet_position, velocity);
+            }
+        }
+        ProjectileType::Piercing => {
+            prototypes::piercing_round(action.entity_mut(ids.new_id()), projectile_position, velocity);
+        }
+        ProjectileType::Explosive => {
+            prototypes::explosive_round(action.entity_mut(ids.new_id()), projectile_position, velocity);
+        }
+        ProjectileType::Silver => {
+            prototypes::silver_bullet(action.entity_mut(ids.new_id()), projectile_position, velocity);
+        }
+    }
+}
+
+/// Refills the magazine from spare rounds, taking longer than a normal turn.
+/// If there is nothing to reload, the action only explains why, and isn't
+/// committed so no time passes.
+pub fn reload(action: &mut EcsAction, entity: EntityRef) {
+
+    const RELOAD_TURN_TIME: u64 = TURN_DURATION_BASE * 2;
+
+    let mut magazine = entity.magazine().expect("Entity missing magazine");
+
+    let message = if magazine.is_full() {
+        action.set_no_commit();
+        ActionMessageType::PlayerMagazineFull
+    } else if magazine.reload() == 0 {
+        action.set_no_commit();
+        ActionMessageType::PlayerOutOfAmmo
+    } else {
+        action.insert_magazine(entity.id(), magazine);
+        action.set_alternative_turn_time(RELOAD_TURN_TIME);
+        ActionMessageType::PlayerReload
+    };
+
+    action.set_action_description(ActionDescription {
+        message: message,
+        coord: entity.position().expect("Entity missing position"),
+    });
 }
 
 pub fn realtime_velocity_move(action: &mut EcsAction, entity: EntityRef, velocity: RealtimeVelocity) {
@@ -110,13 +187,229 @@ pub fn projectile_collision(action: &mut EcsAction, projectile_collision: Projec
     action.set_no_commit();
 }
 
-pub fn damage(action: &mut EcsAction, to_damage: EntityRef, amount: usize) {
+/// Damages an entity, reduced or amplified by its armour and resistances,
+/// returning the amount of damage it took
+pub fn damage(action: &mut EcsAction, to_damage: EntityRef, damage: Damage, source: DamageSource) -> usize {
 
     let mut hit_points = to_damage.hit_points().expect("Entity missing hit_points");
 
+    let armour = to_damage.armour().unwrap_or(0);
+    let resistances = to_damage.resistances().unwrap_or_default();
+
+    let amount = damage.amount_taken(armour, resistances);
     hit_points.dec(amount);
 
     action.insert_hit_points(to_damage.id(), hit_points);
+    action.set_damage_source(source);
+
+    amount
+}
+
+/// Rolls to hit against the victim's evasion, then for a critical hit. A hit
+/// which the victim is immune to has no other effect.
+pub fn melee_attack<R: Rng>(action: &mut EcsAction, attacker: EntityRef, victim: EntityRef, r: &mut R) {
+
+    let attack = attacker.bump_attacker().expect("Entity missing bump_attacker");
+    let evasion = victim.evasion().unwrap_or(0.0);
+
+    let attacker_tile = attacker.tile().expect("Entity missing tile");
+    let victim_tile = victim.tile().expect("Entity missing tile");
+
+    let attacker_position = attacker.position().expect("Entity missing position");
+    let victim_position = victim.position().expect("Entity missing position");
+
+    let message = if r.next_f64() < attack.hit_chance(evasion) {
+        let critical = r.next_f64() < attack.critical_chance;
+        let amount = if critical {
+            damage(action, victim, attack.damage.multiplied(CRITICAL_HIT_MULTIPLIER), DamageSource::Entity(attacker.id()))
+        } else {
+            damage(action, victim, attack.damage, DamageSource::Entity(attacker.id()))
+        };
+
+        if amount == 0 {
+            ActionMessageType::NoEffect(attacker_tile, victim_tile)
+        } else {
+            if let Some(effect) = attacker.inflicts_status_effect() {
+                action.set_status_effect_infliction(StatusEffectInfliction::new(victim.id(), effect));
+            }
+            if critical {
+                if attack.knockback > 0 && !victim.contains_destructible() {
+                    if let Some(direction) = Direction::from_vector(victim_position - attacker_position) {
+                        action.set_knockback(Knockback::new(victim.id(), direction, attack.knockback));
+                    }
+                }
+                ActionMessageType::CriticalHit(attacker_tile, victim_tile)
+            } else {
+                ActionMessageType::Hit(attacker_tile, victim_tile)
+            }
+        }
+    } else {
+        ActionMessageType::Miss(attacker_tile, victim_tile)
+    };
+
+    action.set_action_description(ActionDescription {
+        message: message,
+        coord: victim_position,
+    });
+}
+
+/// Pushes an entity a single cell, recording how much further it will be
+/// pushed so the rest of the push can be scheduled
+pub fn knockback(action: &mut EcsAction, entity: EntityRef, knockback: Knockback) {
+
+    const MS_PER_CELL: u64 = 50;
+
+    // the entity may have been killed by the attack which knocked it back
+    if let Some(position) = entity.position() {
+        action.insert_position(entity.id(), position + knockback.direction.vector());
+        action.set_knockback(Knockback::new(entity.id(), knockback.direction, knockback.distance - 1));
+        action.set_action_time_ms(MS_PER_CELL);
+    }
+}
+
+/// Damages every entity with hit points in the cells visible from the
+/// centre of the explosion, within its radius
+pub fn explode(action: &mut EcsAction,
+               centre: Coord,
+               explosive: Explosive,
+               action_env: ActionEnv,
+               spatial_hash: &SpatialHashTable) {
+
+    let ecs = action_env.ecs;
+    let shadowcast = Shadowcast::new();
+    let mut area = VisibleArea::new();
+
+    // shadowcast only reports cells strictly closer than the given distance
+    shadowcast.observe(centre, spatial_hash, explosive.radius + 1, &mut area, action_env);
+
+    for coord in area.coords() {
+        for entity_id in spatial_hash.get(*coord).entity_id_iter() {
+            if ecs.contains_hit_points(entity_id) {
+                damage(action, ecs.entity(entity_id), explosive.damage, DamageSource::Explosion);
+            }
+        }
+    }
+
+    action.set_action_description(ActionDescription {
+        message: ActionMessageType::Explosion,
+        coord: centre,
+    });
+}
+
+/// Applies a status effect to an entity, or extends it if it's already active
+pub fn apply_status_effect(action: &mut EcsAction, entity: EntityRef, effect: StatusEffect) {
+
+    // the entity may have been killed by whatever caused the effect
+    let position = if let Some(position) = entity.position() {
+        position
+    } else {
+        return;
+    };
+
+    // only entities which take turns are affected
+    if !entity.contains_turn_time() {
+        return;
+    }
+
+    let mut status_effects = entity.status_effects().unwrap_or_default();
+    let already_active = status_effects.is_active(effect.effect_type);
+
+    status_effects.apply(effect);
+    action.insert_status_effects(entity.id(), status_effects);
+
+    if !already_active {
+        if let Some(tile) = entity.tile() {
+            action.set_action_description(ActionDescription {
+                message: ActionMessageType::StatusEffectStart(tile, effect.effect_type),
+                coord: position,
+            });
+        }
+    }
+}
+
+/// Counts down each of an entity's status effects by a turn, applying any
+/// which take effect at the end of a turn
+pub fn tick_status_effects(action: &mut EcsAction, entity: EntityRef) {
+
+    let mut status_effects = if let Some(status_effects) = entity.status_effects() {
+        status_effects
+    } else {
+        return;
+    };
+
+    if status_effects.is_active(StatusEffectType::Bleeding) && entity.contains_hit_points() {
+        damage(action, entity, BLEEDING_DAMAGE, DamageSource::Bleeding);
+    }
+
+    status_effects.tick();
+    action.insert_status_effects(entity.id(), status_effects);
+}
+
+pub fn end_status_effect(action: &mut EcsAction, entity: EntityRef, effect_type: StatusEffectType) {
+    if let (Some(position), Some(tile)) = (entity.position(), entity.tile()) {
+        action.set_action_description(ActionDescription {
+            message: ActionMessageType::StatusEffectEnd(tile, effect_type),
+            coord: position,
+        });
+    }
+}
+
+/// Explains why a terrified entity didn't attack. Nothing changes, so the
+/// action isn't committed.
+pub fn cower(action: &mut EcsAction, entity: EntityRef) {
+    action.set_no_commit();
+
+    if let (Some(position), Some(tile)) = (entity.position(), entity.tile()) {
+        action.set_action_description(ActionDescription {
+            message: ActionMessageType::TooFrightenedToAttack(tile),
+            coord: position,
+        });
+    }
+}
+
+/// Replaces a destroyed door, tree or wall with its broken remains, which
+/// neither block movement nor sight
+pub fn destroy_terrain(action: &mut EcsAction, entity: EntityRef, ecs: &EcsCtx) {
+
+    // several hits at once may each try to destroy the same thing
+    let broken_tile = if let Some(broken_tile) = entity.destructible() {
+        broken_tile
+    } else {
+        return;
+    };
+
+    action.insert_tile(entity.id(), broken_tile);
+    action.insert_opacity(entity.id(), 0.0);
+    action.remove_hit_points(entity.id());
+    action.remove_destructible(entity.id());
+
+    if entity.contains_solid() {
+        action.remove_solid(entity.id());
+    }
+    if entity.contains_bump_attackable() {
+        action.remove_bump_attackable(entity.id());
+    }
+    if entity.contains_door_state() {
+        action.remove_door_state(entity.id());
+    }
+    if entity.contains_you_see() {
+        action.remove_you_see(entity.id());
+    }
+
+    // the remains are no longer changed by the tear
+    if let Some(shadow_id) = entity.shadow_entity() {
+        action.remove_entity(ecs.entity(shadow_id));
+        action.remove_shadow_entity(entity.id());
+        action.remove_transformation_state(entity.id());
+        action.remove_transformation_type(entity.id());
+    }
+
+    if let Some(tile) = entity.tile() {
+        action.set_action_description(ActionDescription {
+            message: ActionMessageType::Destroyed(tile),
+            coord: entity.position().expect("Entity missing position"),
+        });
+    }
 }
 
 pub fn die(action: &mut EcsAction, entity: EntityRef) {
@@ -7,3 +7,6 @@ pub mod actions {
     pub use super::common::*;
     pub use super::transformation::*;
 }
+
+#[cfg(test)]
+mod tests;
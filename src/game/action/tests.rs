@@ -0,0 +1,214 @@
+use rand::{SeedableRng, StdRng};
+
+use ecs::*;
+use game::*;
+use game::data::*;
+use direction::Direction;
+use coord::Coord;
+
+const SEED: usize = 0;
+const NUM_ATTACKS: usize = 100;
+
+const ATTACKER_ID: EntityId = 0;
+const VICTIM_ID: EntityId = 1;
+
+/// An attacker standing next to a victim
+fn duel(attack: MeleeAttack, evasion: f64) -> EcsCtx {
+    let mut action = EcsAction::new();
+
+    {
+        let mut attacker = action.entity_mut(ATTACKER_ID);
+        attacker.insert_position(Coord::new(0, 0));
+        attacker.insert_tile(TileType::Player);
+        attacker.insert_bump_attacker(attack);
+    }
+
+    {
+        let mut victim = action.entity_mut(VICTIM_ID);
+        victim.insert_position(Coord::new(1, 0));
+        victim.insert_tile(TileType::TerrorPillar);
+        victim.insert_hit_points(HitPoints::new(100));
+        victim.insert_evasion(evasion);
+    }
+
+    let mut ecs = EcsCtx::new();
+    ecs.commit(&mut action);
+    ecs
+}
+
+/// The outcome of each of a seeded sequence of attacks, and the hit points
+/// the victim would be left with after each one
+fn attack_outcomes(attack: MeleeAttack, evasion: f64) -> Vec<(ActionMessageType, Option<isize>)> {
+    let ecs = duel(attack, evasion);
+    let mut rng = StdRng::from_seed(&[SEED]);
+    let mut action = EcsAction::new();
+
+    (0..NUM_ATTACKS).map(|_| {
+        actions::melee_attack(&mut action, ecs.entity(ATTACKER_ID), ecs.entity(VICTIM_ID), &mut rng);
+
+        let message = action.clear_action_description().expect("Attack was not described").message;
+        let hit_points = action.hit_points_profile().insertion_copy_iter()
+            .find(|&(entity_id, _)| entity_id == VICTIM_ID)
+            .map(|(_, hit_points)| hit_points.current());
+
+        action.clear();
+
+        (message, hit_points)
+    }).collect()
+}
+
+#[test]
+fn melee_attacks_hit_miss_and_crit() {
+    let outcomes = attack_outcomes(MeleeAttack::new(Damage::physical(1), 0.8, 0.5, 0), 0.0);
+
+    let hit = (ActionMessageType::Hit(TileType::Player, TileType::TerrorPillar), Some(99));
+    let critical = (ActionMessageType::CriticalHit(TileType::Player, TileType::TerrorPillar), Some(98));
+    let miss = (ActionMessageType::Miss(TileType::Player, TileType::TerrorPillar), None);
+
+    assert!(outcomes.contains(&hit));
+    assert!(outcomes.contains(&critical));
+    assert!(outcomes.contains(&miss));
+    assert!(outcomes.iter().all(|outcome| *outcome == hit || *outcome == critical || *outcome == miss));
+}
+
+#[test]
+fn melee_attacks_never_hit_with_full_evasion() {
+    let outcomes = attack_outcomes(MeleeAttack::new(Damage::physical(1), 1.0, 0.5, 0), 1.0);
+
+    let miss = (ActionMessageType::Miss(TileType::Player, TileType::TerrorPillar), None);
+    assert!(outcomes.iter().all(|outcome| *outcome == miss));
+}
+
+#[test]
+fn melee_attacks_never_crit_without_critical_chance() {
+    let outcomes = attack_outcomes(MeleeAttack::new(Damage::physical(1), 1.0, 0.0, 0), 0.0);
+
+    let hit = (ActionMessageType::Hit(TileType::Player, TileType::TerrorPillar), Some(99));
+    assert!(outcomes.iter().all(|outcome| *outcome == hit));
+}
+
+#[test]
+fn critical_hits_knock_the_victim_back() {
+    let ecs = duel(MeleeAttack::new(Damage::physical(1), 1.0, 1.0, 2), 0.0);
+    let mut rng = StdRng::from_seed(&[SEED]);
+
+    let mut action = EcsAction::new();
+    actions::melee_attack(&mut action, ecs.entity(ATTACKER_ID), ecs.entity(VICTIM_ID), &mut rng);
+
+    let knockback = action.knockback().expect("Victim was not knocked back");
+    assert_eq!((knockback.entity_id, knockback.direction, knockback.distance), (VICTIM_ID, Direction::East, 2));
+
+    action.clear();
+    actions::knockback(&mut action, ecs.entity(VICTIM_ID), knockback);
+
+    let position = action.position_profile().insertion_copy_iter()
+        .find(|&(entity_id, _)| entity_id == VICTIM_ID)
+        .map(|(_, position)| position);
+    assert_eq!(position, Some(Coord::new(2, 0)));
+
+    // the rest of the push is left to a later action
+    assert_eq!(action.knockback().map(|knockback| knockback.distance), Some(1));
+}
+
+#[test]
+fn bleeding_does_damage_each_turn() {
+    let mut status_effects = StatusEffects::new();
+    status_effects.apply(StatusEffect::new(StatusEffectType::Bleeding, 2));
+
+    let mut action = EcsAction::new();
+    action.insert_hit_points(VICTIM_ID, HitPoints::new(10));
+    action.insert_status_effects(VICTIM_ID, status_effects);
+
+    let mut ecs = EcsCtx::new();
+    ecs.commit(&mut action);
+
+    actions::tick_status_effects(&mut action, ecs.entity(VICTIM_ID));
+
+    let hit_points = action.hit_points_profile().insertion_copy_iter()
+        .find(|&(entity_id, _)| entity_id == VICTIM_ID)
+        .map(|(_, hit_points)| hit_points.current());
+    assert_eq!(hit_points, Some(10 - BLEEDING_DAMAGE.amount as isize));
+
+    let status_effects = action.status_effects_profile().insertion_copy_iter()
+        .find(|&(entity_id, _)| entity_id == VICTIM_ID)
+        .map(|(_, status_effects)| status_effects.duration(StatusEffectType::Bleeding));
+    assert_eq!(status_effects, Some(1));
+}
+
+#[test]
+fn explosion_damages_everything_in_its_radius() {
+    let centre = Coord::new(2, 2);
+    let positions = [Coord::new(3, 2), Coord::new(4, 2), Coord::new(2, 3), Coord::new(7, 2)];
+
+    let mut action = EcsAction::new();
+    for (entity_id, position) in positions.iter().enumerate() {
+        let mut entity = action.entity_mut(entity_id as EntityId);
+        entity.insert_position(*position);
+        entity.insert_hit_points(HitPoints::new(10));
+    }
+
+    let env = env_with(action, 10, 10);
+
+    let mut action = EcsAction::new();
+    actions::explode(&mut action, centre, Explosive::new(2, Damage::physical(3)), ActionEnv::new(&env.ecs, 1), &env.spatial_hash);
+
+    let mut damaged: Vec<(EntityId, isize)> = action.hit_points_profile().insertion_copy_iter()
+        .map(|(entity_id, hit_points)| (entity_id, hit_points.current()))
+        .collect();
+    damaged.sort();
+
+    // the last entity is outside the radius
+    assert_eq!(damaged, vec![(0, 7), (1, 7), (2, 7)]);
+}
+
+#[test]
+fn melee_attacks_have_no_effect_on_immune_victims() {
+    let mut ecs = duel(MeleeAttack::new(Damage::physical(1), 1.0, 0.5, 2), 0.0);
+    ecs.insert_resistances(VICTIM_ID, Resistances::new().with(DamageType::Physical, Resistance::Immune));
+    let mut rng = StdRng::from_seed(&[SEED]);
+
+    let mut action = EcsAction::new();
+    for _ in 0..NUM_ATTACKS {
+        actions::melee_attack(&mut action, ecs.entity(ATTACKER_ID), ecs.entity(VICTIM_ID), &mut rng);
+
+        let message = action.clear_action_description().expect("Attack was not described").message;
+        assert_eq!(message, ActionMessageType::NoEffect(TileType::Player, TileType::TerrorPillar));
+        assert!(action.knockback().is_none());
+
+        action.clear();
+    }
+}
+
+/// The action of firing with an empty magazine, and its description
+fn fire_empty<F: FnOnce(&mut EcsAction)>(populate: F) -> (EcsAction, Option<ActionDescription>) {
+    let mut action = EcsAction::new();
+    action.insert_position(ATTACKER_ID, Coord::new(0, 0));
+    action.insert_magazine(ATTACKER_ID, Magazine::new(0, 0));
+    populate(&mut action);
+
+    let mut ecs = EcsCtx::new();
+    ecs.commit(&mut action);
+
+    let ids = EntityIdReserver::new();
+    actions::fire_bullet(&mut action, ecs.entity(ATTACKER_ID), Coord::new(1, 0), ProjectileType::Bullet, &ids);
+    let description = action.clear_action_description();
+
+    (action, description)
+}
+
+#[test]
+fn firing_an_empty_magazine_takes_no_time() {
+    let (action, description) = fire_empty(|action| action.insert_pc(ATTACKER_ID));
+
+    assert!(action.contains_no_commit());
+    assert!(action.magazine_profile().insertion_copy_iter().next().is_none());
+    assert_eq!(description.map(|d| d.message), Some(ActionMessageType::PlayerClickEmpty));
+}
+
+#[test]
+fn only_the_player_hears_an_empty_magazine_click() {
+    let (action, description) = fire_empty(|_| {});
+
+    assert!(action.contains_no_commit());
+    assert!(description.is_none());
+}
@@ -6,6 +6,8 @@ pub fn transform_terror_pillar_terror_fly(action: &mut EcsAction, entity: Entity
     action.swap_tile(entity.id(), shadow_id);
     action.swap_turn_time(entity.id(), shadow_id);
     action.swap_transformation_state(entity.id(), shadow_id);
+    action.swap_armour(entity.id(), shadow_id);
+    action.swap_resistances(entity.id(), shadow_id);
 }
 
 pub fn transform_tree(action: &mut EcsAction, entity: EntityRef) {
@@ -18,6 +18,8 @@ pub enum External {
 pub enum MetaAction {
     ActionArgs(ActionArgs),
     External(External),
+    /// Step the game back to the start of the previous player turn
+    Rewind,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -27,7 +29,8 @@ pub enum ActionArgs {
     OpenDoor(EntityId),
     CloseDoor(EntityId),
     Close(EntityId, Direction),
-    FireBullet(EntityId, Coord),
+    FireBullet(EntityId, Coord, ProjectileType),
+    Reload(EntityId),
     RealtimeVelocityMove(EntityId, RealtimeVelocity),
     Destroy(EntityId),
     MoveTear(EntityId),
@@ -40,12 +43,27 @@ pub enum ActionArgs {
     },
     TryLevelSwitch(EntityId),
     ProjectileCollision(ProjectileCollision),
-    Damage(EntityId, usize),
+    Damage(EntityId, Damage, DamageSource),
+    /// The first entity attacks the second
+    MeleeAttack(EntityId, EntityId),
+    /// Push an entity one cell, continuing the push if it has further to go
+    Knockback(Knockback),
     Die(EntityId),
+    /// Replace a piece of terrain with its broken remains
+    DestroyTerrain(EntityId),
+    Explode(Coord, Explosive),
+    ApplyStatusEffect(EntityId, StatusEffect),
+    /// Count down an entity's status effects at the end of its turn
+    TickStatusEffects(EntityId),
+    /// Describe a status effect running out
+    EndStatusEffect(EntityId, StatusEffectType),
+    /// Describe an entity being too frightened to attack
+    Cower(EntityId),
 }
 
 impl ActionArgs {
-    pub fn to_action<R: Rng>(self, action: &mut EcsAction, ecs: &EcsCtx, spatial_hash: &SpatialHashTable, entity_ids: &EntityIdReserver, r: &mut R) {
+    pub fn to_action<R: Rng>(self, action: &mut EcsAction, action_env: ActionEnv, spatial_hash: &SpatialHashTable, entity_ids: &EntityIdReserver, r: &mut R) {
+        let ecs = action_env.ecs;
         match self {
             ActionArgs::Null => (),
             ActionArgs::Walk(entity_id, direction) => {
@@ -60,8 +78,11 @@ impl ActionArgs {
             ActionArgs::Close(entity_id, direction) => {
                 actions::close(action, entity_id, direction);
             }
-            ActionArgs::FireBullet(entity_id, delta) => {
-                actions::fire_bullet(action, ecs.entity(entity_id), delta, entity_ids);
+            ActionArgs::FireBullet(entity_id, delta, projectile_type) => {
+                actions::fire_bullet(action, ecs.entity(entity_id), delta, projectile_type, entity_ids);
+            }
+            ActionArgs::Reload(entity_id) => {
+                actions::reload(action, ecs.entity(entity_id));
             }
             ActionArgs::RealtimeVelocityMove(entity_id, velocity) => {
                 actions::realtime_velocity_move(action, ecs.entity(entity_id), velocity);
@@ -84,12 +105,36 @@ impl ActionArgs {
             ActionArgs::ProjectileCollision(projectile_collision) => {
                 actions::projectile_collision(action, projectile_collision);
             }
-            ActionArgs::Damage(entity_id, amount) => {
-                actions::damage(action, ecs.entity(entity_id), amount);
+            ActionArgs::Damage(entity_id, damage, source) => {
+                actions::damage(action, ecs.entity(entity_id), damage, source);
+            }
+            ActionArgs::MeleeAttack(attacker_id, victim_id) => {
+                actions::melee_attack(action, ecs.entity(attacker_id), ecs.entity(victim_id), r);
+            }
+            ActionArgs::Knockback(knockback) => {
+                actions::knockback(action, ecs.entity(knockback.entity_id), knockback);
             }
             ActionArgs::Die(entity_id) => {
                 actions::die(action, ecs.entity(entity_id));
             }
+            ActionArgs::DestroyTerrain(entity_id) => {
+                actions::destroy_terrain(action, ecs.entity(entity_id), ecs);
+            }
+            ActionArgs::Explode(centre, explosive) => {
+                actions::explode(action, centre, explosive, action_env, spatial_hash);
+            }
+            ActionArgs::ApplyStatusEffect(entity_id, effect) => {
+                actions::apply_status_effect(action, ecs.entity(entity_id), effect);
+            }
+            ActionArgs::TickStatusEffects(entity_id) => {
+                actions::tick_status_effects(action, ecs.entity(entity_id));
+            }
+            ActionArgs::EndStatusEffect(entity_id, effect_type) => {
+                actions::end_status_effect(action, ecs.entity(entity_id), effect_type);
+            }
+            ActionArgs::Cower(entity_id) => {
+                actions::cower(action, ecs.entity(entity_id));
+            }
             ActionArgs::TryLevelSwitch(entity_id) => {
                 actions::try_level_switch(action, entity_id);
             }
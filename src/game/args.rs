@@ -19,18 +19,45 @@ pub fn make_options() -> getopts::Options {
     opts.optopt("f", "frontend", "specify frontend", frontends.as_ref());
     opts.optflag("h", "help", "print this help menu");
     opts.optopt("r", "rngseed", "seed the random number generator with a non-negative integer", "SEED");
+    opts.optopt("", "replay", "play back a replay file recorded during a previous session", "FILE");
+    opts.optopt("", "replay-speed", "multiply the speed of replay playback (0 plays back without delay)", "SPEED");
+    opts.optopt("", "animation-speed", "multiply the speed of realtime animations (0 skips animations)", "SPEED");
+    opts.optopt("", "trace-state", "write a hash of the game state after each turn to a file", "FILE");
+    opts.optopt("p", "profile", &format!("use a named player profile with its own saves and settings (default {})", user_files::DEFAULT_PROFILE), "NAME");
+    opts.optopt("", "slot", "save slot used by --export-save and --import-save (default 1)", "SLOT");
+    opts.optopt("", "export-save", "write the saved game to a human-readable file and exit", "FILE");
+    opts.optopt("", "import-save", "replace the saved game with a file written by --export-save and exit", "FILE");
+    opts.optopt("", "check-state", "compare the game state after each turn with a trace written by --trace-state", "FILE");
 
     opts
 }
 
+/// Conversion between the saved game and its human-readable form
+#[derive(Debug)]
+pub enum SaveTransfer {
+    Export(path::PathBuf),
+    Import(path::PathBuf),
+}
+
 #[derive(Debug)]
 pub struct Arguments {
     pub debug: bool,
     pub frontend: Frontend,
     pub rng_seed: usize,
     pub resource_path: path::PathBuf,
+    /// Directory of the selected profile
     pub user_path: path::PathBuf,
+    /// Directory containing files shared by all profiles
+    pub shared_user_path: path::PathBuf,
+    pub profile: String,
+    pub save_slot: usize,
     pub config: GameConfig,
+    pub replay: Option<Replay>,
+    pub replay_speed: f64,
+    pub animation_speed: f64,
+    pub state_trace_path: Option<path::PathBuf>,
+    pub state_reference: Option<Vec<StateTraceEntry>>,
+    pub save_transfer: Option<SaveTransfer>,
 }
 
 impl Arguments {
@@ -41,7 +68,19 @@ impl Arguments {
             args.debug = true;
         }
 
-        if let Some(rng_seed_str) = matches.opt_str("rngseed") {
+        if let Some(replay_path) = matches.opt_str("replay") {
+            if matches.opt_present("rngseed") {
+                return Err("RNG seed cannot be specified when playing back a replay".to_string());
+            }
+
+            match replay_file::load(&replay_path) {
+                Ok(replay) => {
+                    args.rng_seed = replay.header.seed;
+                    args.replay = Some(replay);
+                }
+                Err(e) => return Err(format!("Failed to load replay {}: {:?}", replay_path, e)),
+            }
+        } else if let Some(rng_seed_str) = matches.opt_str("rngseed") {
             if let Ok(rng_seed) = rng_seed_str.parse::<usize>() {
                 args.rng_seed = rng_seed;
             } else {
@@ -55,6 +94,38 @@ impl Arguments {
             }
         }
 
+        if let Some(replay_speed_str) = matches.opt_str("replay-speed") {
+            match replay_speed_str.parse::<f64>() {
+                Ok(replay_speed) if replay_speed >= 0.0 => args.replay_speed = replay_speed,
+                _ => return Err("Replay speed must be a non-negative number".to_string()),
+            }
+        }
+
+        if let Some(animation_speed_str) = matches.opt_str("animation-speed") {
+            match animation_speed_str.parse::<f64>() {
+                Ok(animation_speed) if animation_speed >= 0.0 => args.animation_speed = animation_speed,
+                _ => return Err("Animation speed must be a non-negative number".to_string()),
+            }
+        }
+
+        if let Some(state_trace_path) = matches.opt_str("trace-state") {
+            args.state_trace_path = Some(path::PathBuf::from(state_trace_path));
+        }
+
+        if let Some(state_reference_path) = matches.opt_str("check-state") {
+            match state_trace_file::load(&state_reference_path) {
+                Ok(entries) => args.state_reference = Some(entries),
+                Err(e) => return Err(format!("Failed to load state trace {}: {:?}", state_reference_path, e)),
+            }
+        }
+
+        match (matches.opt_str("export-save"), matches.opt_str("import-save")) {
+            (Some(_), Some(_)) => return Err("Cannot export and import a save at the same time".to_string()),
+            (Some(path), None) => args.save_transfer = Some(SaveTransfer::Export(path::PathBuf::from(path))),
+            (None, Some(path)) => args.save_transfer = Some(SaveTransfer::Import(path::PathBuf::from(path))),
+            (None, None) => {}
+        }
+
         if let Some(frontend_str) = matches.opt_str("frontend") {
             if let Some(frontend) = Frontend::from_string(frontend_str.as_ref()) {
                 args.frontend = frontend;
@@ -71,8 +142,25 @@ impl Arguments {
             args.resource_path = path;
         }
 
+        if let Some(profile) = matches.opt_str("profile") {
+            if user_files::is_valid_profile_name(&profile) {
+                args.profile = profile;
+            } else {
+                return Err("Profile name must consist of letters, numbers, '-' and '_'".to_string());
+            }
+        }
+
+        if let Some(slot_str) = matches.opt_str("slot") {
+            match slot_str.parse::<usize>() {
+                Ok(slot) if save_slot::slots().any(|s| s == slot) => args.save_slot = slot,
+                _ => return Err(format!("Save slot must be between 1 and {}", save_slot::NUM_SAVE_SLOTS)),
+            }
+        }
+
         if let Some(path) = user_dir_path() {
-            args.user_path = path;
+            args.user_path = user_files::prepare_profile(&path, &args.profile)
+                .map_err(|e| format!("Failed to prepare profile {}: {:?}", args.profile, e))?;
+            args.shared_user_path = path;
         }
 
         args.config = GameConfig::from_file(args.user_path.join(user_files::CONFIG)).unwrap_or_default();
@@ -121,7 +209,16 @@ impl Default for Arguments {
             rng_seed: 0,
             resource_path: path::PathBuf::new(),
             user_path: path::PathBuf::new(),
+            shared_user_path: path::PathBuf::new(),
+            profile: user_files::DEFAULT_PROFILE.to_string(),
+            save_slot: 1,
             config: GameConfig::default(),
+            replay: None,
+            replay_speed: 1.0,
+            animation_speed: 1.0,
+            state_trace_path: None,
+            state_reference: None,
+            save_transfer: None,
         }
     }
 }
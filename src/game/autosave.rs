@@ -0,0 +1,67 @@
+use std::mem;
+use std::path::{Path, PathBuf};
+
+use game::*;
+use game::save_file::SaveFileResult;
+
+/// Default number of player turns between autosaves
+pub const DEFAULT_AUTOSAVE_INTERVAL: u64 = 100;
+
+/// Periodically writes the game in progress to its save slot, so a crash
+/// loses at most a few turns
+pub struct Autosave {
+    interval: u64,
+    slot: Option<(PathBuf, usize)>,
+    last_pc_turns: u64,
+}
+
+impl Autosave {
+    /// An interval of 0 disables autosaving
+    pub fn new(interval: u64) -> Self {
+        Autosave {
+            interval: interval,
+            slot: None,
+            last_pc_turns: 0,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(0)
+    }
+
+    /// Called when a game is started or resumed in a slot
+    pub fn set_slot<P: AsRef<Path>>(&mut self, profile_path: P, slot: usize, game_state: &GameState) {
+        self.slot = Some((profile_path.as_ref().to_path_buf(), slot));
+        self.last_pc_turns = game_state.stats().pc_turns;
+    }
+
+    pub fn clear_slot(&mut self) {
+        self.slot = None;
+    }
+
+    /// Saves the game if enough player turns have passed since the last save.
+    /// Must only be called between turns. If saving fails, the game carries
+    /// on and saving is next attempted after another interval.
+    pub fn save_if_due(&mut self, game_state: &mut GameState) -> SaveFileResult<()> {
+        if self.interval == 0 {
+            return Ok(());
+        }
+
+        if let Some((ref profile_path, slot)) = self.slot {
+            let pc_turns = game_state.stats().pc_turns;
+
+            // rewinding can reduce the number of turns taken
+            if pc_turns.saturating_sub(self.last_pc_turns) >= self.interval {
+                let to_save = mem::replace(game_state, GameState::new());
+                let (saved, result) = save_slot::save(profile_path, slot, to_save);
+                *game_state = saved;
+                self.last_pc_turns = pc_turns;
+                return result;
+            } else if pc_turns < self.last_pc_turns {
+                self.last_pc_turns = pc_turns;
+            }
+        }
+
+        Ok(())
+    }
+}
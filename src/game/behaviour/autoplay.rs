@@ -0,0 +1,100 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
+
+use game::*;
+use game::data::ProjectileType;
+use behaviour::LeafResolution;
+use search::{GridSearchCfg, GridSearchCtx, GridPath};
+use coord::Coord;
+
+/// Number of turns after which the autoplayer gives up on a game
+pub const AUTOPLAY_MAX_TURNS: u64 = 5000;
+
+fn is_exit(cell: &DrawableKnowledgeCell) -> bool {
+    match cell.foreground() {
+        Some(TileType::Book) | Some(TileType::UpStairs) | Some(TileType::DownStairs) => true,
+        _ => false,
+    }
+}
+
+/// Plays the game in place of a human player, using only what the player
+/// can see. Shoots at the nearest visible enemy while it has ammunition,
+/// reloading when the magazine is empty, otherwise heads for an exit it
+/// hasn't used, otherwise explores.
+pub fn autoplay<K: KnowledgeRenderer>() -> BehaviourLeaf<K> {
+    let search_ctx = GridSearchCtx::new();
+    let search_cfg = GridSearchCfg::cardinal_directions();
+    let path = RefCell::new(GridPath::new());
+
+    let turns = Cell::new(0);
+    let used_exits = RefCell::new(HashSet::new());
+    let exit_to_use = Cell::new(None);
+    let last_walk_from = Cell::new(None);
+
+    BehaviourLeaf::new(move |input| {
+        let id = input.entity.id();
+        let position = input.entity.position().unwrap();
+        let here = (input.level_id, position);
+
+        turns.set(turns.get() + 1);
+        if turns.get() > AUTOPLAY_MAX_TURNS {
+            return LeafResolution::Yield(MetaAction::External(External::Quit));
+        }
+
+        if exit_to_use.take() == Some(here) {
+            return LeafResolution::Yield(MetaAction::ActionArgs(ActionArgs::TryLevelSwitch(id)));
+        }
+
+        if last_walk_from.take() == Some(here) {
+            // the last walk didn't move us, so it was blocked or opened a door
+            return LeafResolution::Yield(MetaAction::ActionArgs(ActionArgs::Null));
+        }
+
+        let mut knowledge = input.entity.drawable_knowledge_borrow_mut().unwrap();
+        let level_knowledge = knowledge.level_mut_or_insert_size(input.level_id,
+                                                                 input.spatial_hash.width(),
+                                                                 input.spatial_hash.height());
+
+        if let Some(target) = level_knowledge.sort_targets(position).first().cloned() {
+            match input.entity.magazine() {
+                Some(magazine) if magazine.is_empty() => {
+                    if magazine.reserve() > 0 {
+                        return LeafResolution::Yield(MetaAction::ActionArgs(ActionArgs::Reload(id)));
+                    }
+                }
+                _ => {
+                    let projectile_type = input.entity.projectile_type().unwrap_or(ProjectileType::Bullet);
+                    return LeafResolution::Yield(MetaAction::ActionArgs(ActionArgs::FireBullet(id, target - position, projectile_type)));
+                }
+            }
+        }
+
+        let mut path = path.borrow_mut();
+        let mut used_exits = used_exits.borrow_mut();
+
+        let found_exit = search_ctx.search_predicate(
+            level_knowledge.grid(), position,
+            |info| is_exit(info.cell) && !used_exits.contains(&(input.level_id, info.coord)),
+            &search_cfg, &mut path).is_ok();
+
+        let found = found_exit || search_ctx.search_predicate(
+            level_knowledge.grid(), position,
+            |info| info.cell.last_updated() == 0,
+            &search_cfg, &mut path).is_ok();
+
+        if !found || path.len() == 0 {
+            return LeafResolution::Yield(MetaAction::ActionArgs(ActionArgs::Null));
+        }
+
+        if found_exit && path.len() == 1 {
+            let exit: Coord = path.get_node(0).unwrap().coord;
+            used_exits.insert((input.level_id, exit));
+            exit_to_use.set(Some((input.level_id, exit)));
+        }
+
+        let direction = path.get_node(0).unwrap().direction_to;
+        last_walk_from.set(Some(here));
+
+        LeafResolution::Yield(MetaAction::ActionArgs(ActionArgs::Walk(id, direction)))
+    })
+}
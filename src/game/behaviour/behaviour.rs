@@ -17,6 +17,7 @@ pub struct BehaviourInput<'a, R: 'a + KnowledgeRenderer> {
     pub renderer: &'a RefCell<R>,
     pub rng: &'a GameRng,
     pub language: &'a Box<Language>,
+    pub rule_trace: &'a RuleTrace,
 }
 
 impl<'a, R: KnowledgeRenderer> Clone for BehaviourInput<'a, R> {
@@ -29,6 +30,7 @@ impl<'a, R: KnowledgeRenderer> Clone for BehaviourInput<'a, R> {
             renderer: self.renderer,
             rng: self.rng,
             language: self.language,
+            rule_trace: self.rule_trace,
         }
     }
 }
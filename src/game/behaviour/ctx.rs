@@ -2,7 +2,9 @@ use game::*;
 use game::behaviour::player_input::*;
 use game::behaviour::observation::*;
 use game::behaviour::search::*;
+use game::behaviour::ranged::*;
 use game::behaviour::tear::*;
+use game::behaviour::autoplay::*;
 
 use behaviour::{LeafResolution, CollectionNode};
 
@@ -10,7 +12,9 @@ pub struct BehaviourNodes {
     pub null: BehaviourNodeIndex,
     pub player_input: BehaviourNodeIndex,
     pub simple_npc: BehaviourNodeIndex,
+    pub ranged_npc: BehaviourNodeIndex,
     pub tear: BehaviourNodeIndex,
+    pub autoplay: BehaviourNodeIndex,
 }
 
 pub struct BehaviourCtx<K: KnowledgeRenderer> {
@@ -24,7 +28,9 @@ impl BehaviourNodes {
             BehaviourType::Null => self.null,
             BehaviourType::PlayerInput => self.player_input,
             BehaviourType::SimpleNpc => self.simple_npc,
+            BehaviourType::RangedNpc => self.ranged_npc,
             BehaviourType::Tear => self.tear,
+            BehaviourType::Autoplay => self.autoplay,
         }
     }
 }
@@ -46,13 +52,21 @@ impl<K: KnowledgeRenderer> BehaviourCtx<K> {
 
         let simple_npc = graph.add_switch(simple_npc_shadowcast(simple_npc_loop));
 
+        let ranged_npc_leaf = graph.add_leaf(ranged_npc_shoot_or_reposition());
+        let ranged_npc_loop = graph.add_collection(CollectionNode::Forever(ranged_npc_leaf));
+        let ranged_npc = graph.add_switch(simple_npc_shadowcast(ranged_npc_loop));
+
         let move_tear = graph.add_leaf(move_tear());
 
+        let autoplay_leaf = graph.add_leaf(autoplay());
+
         let nodes = BehaviourNodes {
             null: graph.add_collection(CollectionNode::Forever(null_leaf)),
             player_input: graph.add_collection(CollectionNode::Forever(player_input_leaf)),
             simple_npc: graph.add_collection(CollectionNode::Forever(simple_npc)),
+            ranged_npc: graph.add_collection(CollectionNode::Forever(ranged_npc)),
             tear: graph.add_collection(CollectionNode::Forever(move_tear)),
+            autoplay: graph.add_collection(CollectionNode::Forever(autoplay_leaf)),
         };
 
         BehaviourCtx {
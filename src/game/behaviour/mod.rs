@@ -10,4 +10,9 @@ pub use self::types::*;
 mod player_input;
 mod observation;
 mod search;
+mod ranged;
 mod tear;
+mod autoplay;
+
+#[cfg(test)]
+mod tests;
@@ -9,6 +9,7 @@ pub fn simple_npc_shadowcast<K: KnowledgeRenderer>(child: BehaviourNodeIndex) ->
 
         let eye = input.entity.position().unwrap();
         let vision_distance = input.entity.vision_distance().unwrap();
+        let vision_distance = input.entity.status_effects().map_or(vision_distance, |s| s.modify_vision_distance(vision_distance));
         let mut knowledge = input.entity.simple_npc_knowledge_borrow_mut().unwrap();
         let level_knowledge = knowledge.level_mut_or_insert_size(input.level_id,
                                                                  input.spatial_hash.width(),
@@ -1,7 +1,8 @@
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 use std::cmp;
 
 use game::*;
+use game::data::ProjectileType;
 use behaviour::LeafResolution;
 use direction::Direction;
 use coord::{Coord, StraightLine};
@@ -31,8 +32,9 @@ fn control_to_direction(control: Control) -> Option<Direction> {
     }
 }
 
-fn aim<R: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<R>, map: &ControlMap, mut input_source: I) -> Option<Coord> {
+fn aim<R: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<R>, map: &ControlMap, mut input_source: I) -> Option<(Coord, ProjectileType)> {
     let start = input.entity.position().unwrap();
+    let mut projectile_type = input.entity.projectile_type().unwrap_or(ProjectileType::Bullet);
     let mut knowledge = input.entity.drawable_knowledge_borrow_mut().unwrap();
     let level_knowledge = knowledge.level_mut_or_insert_size(input.level_id,
                                                              input.spatial_hash.width(),
@@ -48,11 +50,17 @@ fn aim<R: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<R>, map: &Con
     };
 
     let mut renderer = input.renderer.borrow_mut();
+    let mut message_log = input.entity.message_log_borrow_mut().unwrap();
+
+    let mut result = None;
 
     loop {
 
+        message_log.add_temporary(MessageType::SelectedProjectile(projectile_type));
+        renderer.update_log_buffer(message_log.deref(), input.language);
+
         let overlay = RenderOverlay::AimLine(StraightLine::new(start, end));
-        renderer.publish_game_window_with_overlay(&overlay);
+        renderer.publish_all_windows_with_overlay(input.entity, input.language, &overlay);
 
         if let Some(event) = input_source.next_input() {
             if let Some(control) = map.get(event) {
@@ -71,9 +79,11 @@ fn aim<R: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<R>, map: &Con
                         target_idx = (target_idx + targets.len() - 1) % targets.len();
                         end = targets[target_idx];
                     }
+                } else if control == Control::SwitchProjectile {
+                    projectile_type = projectile_type.next();
                 } else if control == Control::Fire || control == Control::Use {
-                    renderer.publish_game_window();
-                    return Some(end);
+                    result = Some((end, projectile_type));
+                    break;
                 } else {
                     break;
                 }
@@ -83,8 +93,11 @@ fn aim<R: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<R>, map: &Con
         }
     }
 
-    renderer.publish_game_window();
-    None
+    message_log.add_temporary(MessageType::Empty);
+    renderer.update_log_buffer(message_log.deref(), input.language);
+    renderer.publish_all_windows(input.entity, input.language);
+
+    result
 }
 
 fn display_message_log<K: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<K>, mut input_source: I, map: &ControlMap) {
@@ -126,6 +139,83 @@ fn display_message_log<K: KnowledgeRenderer, I: InputSource>(input: BehaviourInp
     renderer.publish_all_windows(input.entity, input.language);
 }
 
+fn display_rule_trace<K: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<K>, mut input_source: I, map: &ControlMap) {
+
+    let mut renderer = input.renderer.borrow_mut();
+
+    let mut message = Message::new();
+    input.language.translate(MessageType::RuleTrace, &mut message);
+    message.push(MessagePart::Newline);
+
+    if input.rule_trace.entries().is_empty() {
+        message.push(MessagePart::Newline);
+        input.language.translate(MessageType::RuleTraceEmpty, &mut message);
+    }
+
+    // most recent first
+    for entry in input.rule_trace.entries().iter().rev() {
+        message.push(MessagePart::Newline);
+        for line in entry.to_string().lines() {
+            message.push(MessagePart::Text(TextMessagePart::Plain(line.to_string())));
+            message.push(MessagePart::Newline);
+        }
+    }
+
+    let mut wrapped = Vec::new();
+    renderer.fullscreen_wrap(&message, &mut wrapped);
+
+    let num_lines = renderer.fullscreen_log_num_rows();
+    let max_offset = if wrapped.len() > num_lines {
+        wrapped.len() - num_lines
+    } else {
+        0
+    };
+
+    let mut offset = 0;
+
+    loop {
+        renderer.publish_fullscreen_translated_message(&message, offset);
+
+        if let Some(event) = input_source.next_input() {
+            if let Some(control) = map.get(event) {
+                match control {
+                    Control::Pause |
+                        Control::DisplayRuleTrace => break,
+                    Control::Direction(Direction::North) => {
+                        if offset > 0 {
+                            offset -= 1;
+                        }
+                    }
+                    Control::Direction(Direction::South) => {
+                        offset = cmp::min(max_offset, offset + 1);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    renderer.publish_all_windows(input.entity, input.language);
+}
+
+fn display_character_stats<K: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<K>, mut input_source: I) {
+
+    let mut renderer = input.renderer.borrow_mut();
+    let stats = input.entity.character_stats().unwrap_or_default();
+
+    let mut message = Message::new();
+    input.language.translate(MessageType::CharacterStats, &mut message);
+    message.push(MessagePart::Newline);
+    message.push(MessagePart::Newline);
+    input.language.translate_character_stats(&stats, &mut message);
+    message.push(MessagePart::Newline);
+    input.language.translate(MessageType::PressAnyKey, &mut message);
+
+    display_message_scrolling(renderer.deref_mut(), &mut input_source, &message, true);
+
+    renderer.publish_all_windows(input.entity, input.language);
+}
+
 fn examine<K: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<K>, mut input_source: I, map: &ControlMap) {
 
 
@@ -212,21 +302,33 @@ fn get_meta_action<K: KnowledgeRenderer, I: InputSource>(input: BehaviourInput<K
                         get_direction(map, input_source).map(|d| MetaAction::ActionArgs(ActionArgs::Close(input.entity.id(), d)))
                     }
                     Control::Fire => {
-                        aim(input, map, input_source).map(|coord| {
+                        aim(input, map, input_source).map(|(coord, projectile_type)| {
                             let delta = coord - input.entity.position().unwrap();
 
 
-                            MetaAction::ActionArgs(ActionArgs::FireBullet(input.entity.id(), delta))
+                            MetaAction::ActionArgs(ActionArgs::FireBullet(input.entity.id(), delta, projectile_type))
                         })
                     }
+                    Control::Reload => {
+                        Some(MetaAction::ActionArgs(ActionArgs::Reload(input.entity.id())))
+                    }
                     Control::Wait => {
                         Some(MetaAction::ActionArgs(ActionArgs::Null))
                     }
                     Control::Pause => Some(MetaAction::External(External::Pause)),
+                    Control::Rewind => Some(MetaAction::Rewind),
                     Control::DisplayMessageLog => {
                         display_message_log(input, input_source, map);
                         None
                     }
+                    Control::DisplayRuleTrace => {
+                        display_rule_trace(input, input_source, map);
+                        None
+                    }
+                    Control::DisplayCharacterStats => {
+                        display_character_stats(input, input_source);
+                        None
+                    }
                     Control::Examine => {
                         examine(input, input_source, map);
                         None
@@ -0,0 +1,76 @@
+use std::cell::RefCell;
+
+use game::*;
+use game::data::ProjectileType;
+use behaviour::LeafResolution;
+use search::{GridSearchCfg, GridSearchCtx, GridPath};
+use coord::{Coord, StraightLine};
+
+/// True if nothing the entity knows to be solid, and no other enemy it knows
+/// of, lies strictly between `from` and `to`
+fn has_line_of_fire(level_knowledge: &SimpleNpcKnowledgeLevel, from: Coord, to: Coord) -> bool {
+    StraightLine::new(from, to).iter()
+        .filter(|coord| *coord != from && *coord != to)
+        .all(|coord| {
+            let cell = level_knowledge.get_with_default(coord);
+            !cell.solid() && !cell.enemy()
+        })
+}
+
+/// The closest cell the player was in when last seen. Ties are broken by
+/// position so the choice doesn't depend on the order targets are stored.
+fn closest_target(level_knowledge: &SimpleNpcKnowledgeLevel, position: Coord) -> Option<Coord> {
+    level_knowledge.targets()
+        .filter(|target| level_knowledge.get_with_default(**target).pc())
+        .min_by_key(|target| (target.square_distance(position), target.y, target.x))
+        .cloned()
+}
+
+/// Shoots at the closest known target if nothing is in the way, otherwise
+/// walks towards the nearest cell with a clear line of fire
+pub fn shoot_or_reposition(id: EntityId,
+                           position: Coord,
+                           level_knowledge: &SimpleNpcKnowledgeLevel,
+                           search_ctx: &GridSearchCtx,
+                           search_cfg: &GridSearchCfg,
+                           path: &mut GridPath) -> ActionArgs {
+
+    let target = if let Some(target) = closest_target(level_knowledge, position) {
+        target
+    } else {
+        return ActionArgs::Null;
+    };
+
+    if has_line_of_fire(level_knowledge, position, target) {
+        return ActionArgs::FireBullet(id, target - position, ProjectileType::Bullet);
+    }
+
+    let found = search_ctx.search_predicate(
+        level_knowledge.grid(), position,
+        |info| has_line_of_fire(level_knowledge, info.coord, target),
+        search_cfg, path).is_ok();
+
+    if !found || path.len() == 0 {
+        return ActionArgs::Null;
+    }
+
+    ActionArgs::Walk(id, path.get_node(0).unwrap().direction_to)
+}
+
+/// Decides each turn whether to shoot or reposition
+pub fn ranged_npc_shoot_or_reposition<K: KnowledgeRenderer>() -> BehaviourLeaf<K> {
+    let search_ctx = GridSearchCtx::new();
+    let search_cfg = GridSearchCfg::cardinal_directions();
+    let path = RefCell::new(GridPath::new());
+
+    BehaviourLeaf::new(move |input| {
+        let id = input.entity.id();
+        let position = input.entity.position().unwrap();
+        let knowledge = input.entity.simple_npc_knowledge_borrow().unwrap();
+        let level_knowledge = knowledge.level(input.level_id);
+
+        let args = shoot_or_reposition(id, position, level_knowledge, &search_ctx, &search_cfg, &mut path.borrow_mut());
+
+        LeafResolution::Yield(MetaAction::ActionArgs(args))
+    })
+}
@@ -0,0 +1,103 @@
+use ecs::*;
+use game::*;
+use search::{GridSearchCfg, GridSearchCtx, GridPath};
+use util::TwoDimensionalCons;
+use coord::Coord;
+
+use super::ranged;
+
+const HUNTER_ID: EntityId = 0;
+const TARGET_ID: EntityId = 1;
+const OBSTACLE_ID: EntityId = 2;
+
+const HUNTER_COORD: Coord = Coord { x: 0, y: 1 };
+const TARGET_COORD: Coord = Coord { x: 4, y: 1 };
+const OBSTACLE_COORD: Coord = Coord { x: 2, y: 1 };
+
+const WIDTH: usize = 5;
+const HEIGHT: usize = 3;
+
+enum Obstacle {
+    Nothing,
+    Wall,
+    Enemy,
+}
+
+/// What a hunter does when it can see the player on the other side of an
+/// open room, with an obstacle between them
+fn hunter_action(obstacle: Obstacle) -> ActionArgs {
+    let mut action = EcsAction::new();
+
+    action.insert_position(HUNTER_ID, HUNTER_COORD);
+    action.insert_enemy(HUNTER_ID);
+
+    action.insert_position(TARGET_ID, TARGET_COORD);
+    action.insert_pc(TARGET_ID);
+
+    match obstacle {
+        Obstacle::Nothing => {}
+        Obstacle::Wall => {
+            action.insert_position(OBSTACLE_ID, OBSTACLE_COORD);
+            action.insert_solid(OBSTACLE_ID);
+        }
+        Obstacle::Enemy => {
+            action.insert_position(OBSTACLE_ID, OBSTACLE_COORD);
+            action.insert_enemy(OBSTACLE_ID);
+        }
+    }
+
+    let env = env_with(action, WIDTH, HEIGHT);
+
+    let action_env = ActionEnv {
+        ecs: &env.ecs,
+        id: 1,
+    };
+
+    let mut level_knowledge = SimpleNpcKnowledgeLevel::new(WIDTH, HEIGHT);
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let coord = Coord::new(x as isize, y as isize);
+            level_knowledge.update_cell(coord, env.spatial_hash.get(coord), 1.0, action_env);
+        }
+    }
+
+    let search_ctx = GridSearchCtx::new();
+    let search_cfg = GridSearchCfg::cardinal_directions();
+    let mut path = GridPath::new();
+
+    ranged::shoot_or_reposition(HUNTER_ID, HUNTER_COORD, &level_knowledge, &search_ctx, &search_cfg, &mut path)
+}
+
+fn is_walk(args: ActionArgs) -> bool {
+    match args {
+        ActionArgs::Walk(entity_id, _) => entity_id == HUNTER_ID,
+        _ => false,
+    }
+}
+
+#[test]
+fn hunters_fire_along_a_clear_line() {
+    let args = hunter_action(Obstacle::Nothing);
+
+    match args {
+        ActionArgs::FireBullet(entity_id, delta, _) => {
+            assert_eq!(entity_id, HUNTER_ID);
+            assert_eq!(delta, TARGET_COORD - HUNTER_COORD);
+        }
+        other => panic!("Expected the hunter to fire, found {:?}", other),
+    }
+}
+
+#[test]
+fn walls_block_the_line_of_fire() {
+    let args = hunter_action(Obstacle::Wall);
+
+    assert!(is_walk(args), "Expected the hunter to move, found {:?}", args);
+}
+
+#[test]
+fn other_enemies_block_the_line_of_fire() {
+    let args = hunter_action(Obstacle::Enemy);
+
+    assert!(is_walk(args), "Expected the hunter to move, found {:?}", args);
+}
@@ -3,5 +3,7 @@ pub enum BehaviourType {
     Null,
     PlayerInput,
     SimpleNpc,
+    RangedNpc,
     Tear,
+    Autoplay,
 }
@@ -0,0 +1,57 @@
+use std::thread;
+use std::time::Duration;
+
+/// Source of the delays used to animate realtime effects such as
+/// projectiles in flight
+pub trait Clock {
+    fn sleep(&self, duration: Duration);
+}
+
+/// Waits for the full duration
+pub struct RealClock;
+
+/// Doesn't wait at all
+pub struct InstantClock;
+
+/// Waits for the duration divided by a speed multiplier
+pub struct ScaledClock {
+    speed: f64,
+}
+
+impl Clock for RealClock {
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+impl Clock for InstantClock {
+    fn sleep(&self, _duration: Duration) {}
+}
+
+impl ScaledClock {
+    pub fn new(speed: f64) -> Self {
+        assert!(speed > 0.0, "Clock speed must be positive");
+        ScaledClock {
+            speed: speed,
+        }
+    }
+}
+
+impl Clock for ScaledClock {
+    fn sleep(&self, duration: Duration) {
+        let nanos = (duration.as_secs() as f64 * 1e9 + duration.subsec_nanos() as f64) / self.speed;
+        let nanos = nanos as u64;
+        thread::sleep(Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32));
+    }
+}
+
+/// Selects a clock for a speed multiplier. A speed of 0 doesn't wait at all.
+pub fn clock_for_speed(speed: f64) -> Box<Clock> {
+    if speed == 0.0 {
+        Box::new(InstantClock)
+    } else if speed == 1.0 {
+        Box::new(RealClock)
+    } else {
+        Box::new(ScaledClock::new(speed))
+    }
+}
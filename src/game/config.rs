@@ -4,12 +4,18 @@ use game::*;
 #[derive(Debug, Deserialize)]
 pub struct GameConfig {
     pub graphics: GraphicsConfig,
+    #[serde(default)]
+    pub save: SaveConfig,
+    #[serde(default)]
+    pub rules: RulesConfig,
 }
 
 impl Default for GameConfig {
     fn default() -> Self {
         GameConfig {
             graphics: GraphicsConfig::default(),
+            save: SaveConfig::default(),
+            rules: RulesConfig::default(),
         }
     }
 }
@@ -27,6 +33,28 @@ impl Default for GraphicsConfig {
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SaveConfig {
+    /// Number of player turns between autosaves. 0 disables autosaving.
+    pub autosave_interval: u64,
+}
+
+impl Default for SaveConfig {
+    fn default() -> Self {
+        SaveConfig {
+            autosave_interval: DEFAULT_AUTOSAVE_INTERVAL,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RulesConfig {
+    /// Names of rules, as shown in the rule trace, which are disabled in
+    /// normal games
+    #[serde(default)]
+    pub disabled: Vec<String>,
+}
+
 impl GameConfig {
     pub fn from_file<P: AsRef<path::Path>>(path: P) -> Option<Self> {
         game_file::read_toml(path).ok()
@@ -1,6 +1,8 @@
 use std::collections::{hash_map, HashMap};
 use std::slice;
 
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
 use game::InputEvent;
 use game::control_spec::ControlSpec;
 use direction::Direction;
@@ -10,16 +12,31 @@ pub enum Control {
     Direction(Direction),
     Use,
     Fire,
+    Reload,
+    SwitchProjectile,
     NextTarget,
     PrevTarget,
     Close,
     Wait,
     DisplayMessageLog,
+    DisplayCharacterStats,
+    DisplayRuleTrace,
     Examine,
     Pause,
+    Rewind,
+}
+
+impl Control {
+    /// Controls which are only bound in debug mode
+    pub fn is_debug(self) -> bool {
+        match self {
+            Control::Rewind | Control::DisplayRuleTrace => true,
+            _ => false,
+        }
+    }
 }
 
-const NUM_CONTROLS: usize = 13;
+const NUM_CONTROLS: usize = 18;
 const CONTROL_ORDER: [Control; NUM_CONTROLS] = [
     Control::Direction(Direction::North),
     Control::Direction(Direction::South),
@@ -29,11 +46,16 @@ const CONTROL_ORDER: [Control; NUM_CONTROLS] = [
     Control::Wait,
     Control::Close,
     Control::Fire,
+    Control::Reload,
+    Control::SwitchProjectile,
     Control::NextTarget,
     Control::PrevTarget,
     Control::Examine,
     Control::DisplayMessageLog,
+    Control::DisplayCharacterStats,
     Control::Pause,
+    Control::Rewind,
+    Control::DisplayRuleTrace,
 ];
 
 pub struct ControlMapIter<'a>(hash_map::Iter<'a, InputEvent, Control>);
@@ -46,9 +68,24 @@ impl<'a> Iterator for ControlMapIter<'a> {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ControlMap {
+    // input events can't be used as keys in text formats, so the map is
+    // serialized as a sequence of pairs
+    #[serde(serialize_with = "serialize_pairs", deserialize_with = "deserialize_pairs")]
     map: HashMap<InputEvent, Control>,
 }
 
+fn serialize_pairs<S: Serializer>(map: &HashMap<InputEvent, Control>, serializer: S) -> Result<S::Ok, S::Error> {
+    // sorted so the serialized form doesn't depend on the iteration order of the map
+    let mut pairs: Vec<(InputEvent, Control)> = map.iter().map(|(i, c)| (*i, *c)).collect();
+    pairs.sort_by_key(|&(input, _)| input);
+    pairs.serialize(serializer)
+}
+
+fn deserialize_pairs<D: Deserializer>(deserializer: D) -> Result<HashMap<InputEvent, Control>, D::Error> {
+    let pairs: Vec<(InputEvent, Control)> = Deserialize::deserialize(deserializer)?;
+    Ok(pairs.into_iter().collect())
+}
+
 impl ControlMap {
     pub fn get(&self, event: InputEvent) -> Option<Control> {
         self.map.get(&event).map(|r| *r)
@@ -86,10 +123,30 @@ impl ControlMap {
         self.insert(InputEvent::Char('.'), Control::Wait);
 
         self.insert(InputEvent::Char('f'), Control::Fire);
+        self.insert(InputEvent::Char('r'), Control::Reload);
+        self.insert(InputEvent::Char('a'), Control::SwitchProjectile);
         self.insert(InputEvent::Char('n'), Control::NextTarget);
         self.insert(InputEvent::Char('N'), Control::PrevTarget);
 
         self.insert(InputEvent::Char('t'), Control::DisplayMessageLog);
+        self.insert(InputEvent::Char('s'), Control::DisplayCharacterStats);
+    }
+
+    /// Binds the debug controls which aren't already bound, to inputs which
+    /// aren't already used
+    pub fn add_debug_defaults(&mut self) {
+        self.insert_if_unbound(InputEvent::Char('u'), Control::Rewind);
+        self.insert_if_unbound(InputEvent::Char('R'), Control::DisplayRuleTrace);
+    }
+
+    pub fn remove_debug_controls(&mut self) {
+        self.map.retain(|_, control| !control.is_debug());
+    }
+
+    fn insert_if_unbound(&mut self, input: InputEvent, control: Control) {
+        if self.get(input).is_none() && !self.map.values().any(|c| *c == control) {
+            self.insert(input, control);
+        }
     }
 
     pub fn descriptions(&self) -> ControlDescriptions {
@@ -105,10 +105,15 @@ impl<'a> From<&'a String> for Control {
             "Examine" => Control::Examine,
             "Wait" => Control::Wait,
             "Fire" => Control::Fire,
+            "Reload" => Control::Reload,
+            "SwitchProjectile" => Control::SwitchProjectile,
             "NextTarget" => Control::NextTarget,
             "PrevTarget" => Control::PrevTarget,
             "DisplayMessageLog" => Control::DisplayMessageLog,
+            "DisplayCharacterStats" => Control::DisplayCharacterStats,
             "Pause" => Control::Pause,
+            "Rewind" => Control::Rewind,
+            "DisplayRuleTrace" => Control::DisplayRuleTrace,
             _ => panic!("No such control: {}", s),
         }
     }
@@ -126,10 +131,15 @@ impl From<Control> for String {
             Control::Examine => "Examine",
             Control::Wait => "Wait",
             Control::Fire => "Fire",
+            Control::Reload => "Reload",
+            Control::SwitchProjectile => "SwitchProjectile",
             Control::NextTarget => "NextTarget",
             Control::PrevTarget => "PrevTarget",
             Control::DisplayMessageLog => "DisplayMessageLog",
+            Control::DisplayCharacterStats => "DisplayCharacterStats",
             Control::Pause => "Pause",
+            Control::Rewind => "Rewind",
+            Control::DisplayRuleTrace => "DisplayRuleTrace",
             _ => panic!("Unencodable control: {:?}", control),
         }.to_string()
     }
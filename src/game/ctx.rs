@@ -1,5 +1,6 @@
 use std::cell::RefCell;
 use std::ops::DerefMut;
+use std::path::Path;
 
 use game::*;
 use game::data::*;
@@ -36,10 +37,13 @@ impl From<SerializableEntityIdReserver> for EntityIdReserver {
 
 enum MainMenuSelection {
     NewGame,
+    DailyChallenge,
+    LoadGame,
     Quit,
     Continue,
     SaveAndQuit,
     Controls,
+    HighScores,
 }
 
 pub enum GameOverReason {
@@ -57,6 +61,10 @@ pub struct GameCtx<Renderer: KnowledgeRenderer, Input: InputSource> {
     input_source: Input,
     pc_observer: Shadowcast,
     behaviour_ctx: BehaviourCtx<Renderer>,
+    rules: RuleRegistry,
+    rules_config: RulesConfig,
+    rule_trace: RuleTrace,
+    event_bus: EventBus,
     rule_reactions: Vec<Reaction>,
     ecs_action: EcsAction,
     action_schedule: Schedule<ActionArgs>,
@@ -64,6 +72,13 @@ pub struct GameCtx<Renderer: KnowledgeRenderer, Input: InputSource> {
     height: usize,
     rng: GameRng,
     language: Box<Language>,
+    clock: Box<Clock>,
+    journal: UndoJournal,
+    autosave: Autosave,
+    daily_challenge_day: Option<u64>,
+    /// Seed of the next normal game started in this session
+    next_seed: usize,
+    state_trace: StateTrace,
 }
 
 #[derive(Clone, Copy, Serialize, Deserialize)]
@@ -72,24 +87,107 @@ pub struct GlobalIds {
     level_id: LevelId,
 }
 
+/// Running totals kept over the course of a game
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct GameStats {
+    pub pc_turns: u64,
+    pub enemies_killed: u64,
+    /// What dealt the player's killing blow
+    pub killed_by: Option<DeathCause>,
+}
+
+/// What killed the player
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeathCause {
+    /// Tile of the attacker or projectile which dealt the killing blow
+    Killer(TileType),
+    Bleeding,
+    Explosion,
+    /// Being knocked into something
+    Impact,
+}
+
+/// The kind of game being played
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameMode {
+    Normal,
+    /// The daily challenge for a day, counted from the unix epoch
+    Daily(u64),
+}
+
 pub struct GameState {
     levels: LevelTable,
     global_ids: Option<GlobalIds>,
     entity_ids: EntityIdReserver,
     turn_id: u64,
     action_id: ActionId,
+    stats: GameStats,
+    mode: GameMode,
+    seed: usize,
 }
 
 impl GameState {
     pub fn new() -> Self {
+        Self::new_with_mode(GameMode::Normal, 0)
+    }
+
+    /// The seed is the one the random number generator was seeded with when
+    /// the game started
+    pub fn new_with_mode(mode: GameMode, seed: usize) -> Self {
         GameState {
             levels: LevelTable::new(),
             global_ids: None,
             entity_ids: EntityIdReserver::new(),
             turn_id: 0,
             action_id: 0,
+            stats: GameStats::default(),
+            mode: mode,
+            seed: seed,
+        }
+    }
+
+    pub fn mode(&self) -> GameMode {
+        self.mode
+    }
+
+    pub fn seed(&self) -> usize {
+        self.seed
+    }
+
+    pub fn stats(&self) -> &GameStats {
+        &self.stats
+    }
+
+    pub fn level_id(&self) -> Option<LevelId> {
+        self.global_ids.map(|ids| ids.level_id)
+    }
+
+    pub fn pc_entity(&self) -> Option<EntityRef> {
+        self.global_ids.map(|ids| self.levels.level(ids.level_id).ecs.entity(ids.pc_id))
+    }
+
+    pub fn num_levels(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// The player's id, and the level they're on. Lets tests set up
+    /// situations which are hard to reach by playing.
+    #[cfg(test)]
+    pub fn pc_level_mut(&mut self) -> Option<(EntityId, &mut Level)> {
+        match self.global_ids {
+            Some(GlobalIds { pc_id, level_id }) => Some((pc_id, self.levels.level_mut(level_id))),
+            None => None,
         }
     }
+
+    /// Commits an action to the level the player is on, as though it were
+    /// taken during the game
+    #[cfg(test)]
+    pub fn commit_to_pc_level(&mut self, action: &mut EcsAction) {
+        let level_id = self.global_ids.expect("Player is missing").level_id;
+        self.levels.level_mut(level_id).commit(action, self.action_id);
+        self.action_id += 1;
+    }
 }
 
 #[derive(Serialize, Deserialize)]
@@ -99,30 +197,39 @@ pub struct SerializableGameState {
     entity_ids: SerializableEntityIdReserver,
     turn_id: u64,
     action_id: ActionId,
+    stats: GameStats,
+    mode: GameMode,
+    seed: usize,
 }
 
 impl From<GameState> for SerializableGameState {
     fn from(game_state: GameState) -> Self {
-        let GameState { levels, global_ids, entity_ids, turn_id, action_id } = game_state;
+        let GameState { levels, global_ids, entity_ids, turn_id, action_id, stats, mode, seed } = game_state;
         SerializableGameState {
             levels: SerializableLevelTable::from(levels),
             global_ids: global_ids,
             entity_ids: SerializableEntityIdReserver::from(entity_ids),
             turn_id: turn_id,
             action_id: action_id,
+            stats: stats,
+            mode: mode,
+            seed: seed,
         }
     }
 }
 
 impl From<SerializableGameState> for GameState {
     fn from(game_state: SerializableGameState) -> Self {
-        let SerializableGameState { levels, global_ids, entity_ids, turn_id, action_id } = game_state;
+        let SerializableGameState { levels, global_ids, entity_ids, turn_id, action_id, stats, mode, seed } = game_state;
         GameState {
             levels: LevelTable::from(levels),
             global_ids: global_ids,
             entity_ids: EntityIdReserver::from(entity_ids),
             turn_id: turn_id,
             action_id: action_id,
+            stats: stats,
+            mode: mode,
+            seed: seed,
         }
     }
 }
@@ -134,6 +241,10 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
             input_source: input_source.clone(),
             pc_observer: Shadowcast::new(),
             behaviour_ctx: BehaviourCtx::new(input_source),
+            rules: RuleRegistry::standard(),
+            rules_config: RulesConfig::default(),
+            rule_trace: RuleTrace::disabled(),
+            event_bus: EventBus::new(),
             rule_reactions: Vec::new(),
             ecs_action: EcsAction::new(),
             action_schedule: Schedule::new(),
@@ -141,19 +252,67 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
             height: height,
             rng: GameRng::new(seed),
             language: Box::new(languages::English),
+            clock: Box::new(RealClock),
+            journal: UndoJournal::disabled(),
+            autosave: Autosave::disabled(),
+            daily_challenge_day: None,
+            next_seed: seed,
+            state_trace: StateTrace::disabled(),
         }
     }
 
+    pub fn set_clock(&mut self, clock: Box<Clock>) {
+        self.clock = clock;
+    }
+
+    /// Fixes the day of the daily challenge, rather than using the current date
+    pub fn set_daily_challenge_day(&mut self, day: u64) {
+        self.daily_challenge_day = Some(day);
+    }
+
+    /// Registers a listener to be notified of every action committed
+    /// during the game
+    pub fn subscribe(&mut self, listener: Box<CommitListener>) {
+        self.event_bus.subscribe(listener);
+    }
+
+    pub fn set_state_trace(&mut self, state_trace: StateTrace) {
+        self.state_trace = state_trace;
+    }
+
+    pub fn state_divergence(&self) -> Option<StateDivergence> {
+        self.state_trace.divergence()
+    }
+
     pub fn run(&mut self, args: Arguments) -> GameResult<()> {
 
-        let mut current_game_state = save_file::load(args.user_path.as_path());
+        if args.debug {
+            self.journal = UndoJournal::new(UNDO_JOURNAL_MAX_TURNS);
+            self.rule_trace = RuleTrace::new(RULE_TRACE_MAX_ENTRIES);
+        }
+
+        self.autosave = Autosave::new(args.config.save.autosave_interval);
+        self.rules_config = args.config.rules.clone();
+
+        let profile_path = args.user_path.as_path();
+
+        // resume the most recently played save slot
+        let mut current_slot = save_slot::most_recent(profile_path);
+        let (mut current_game_state, mut load_error) = match current_slot.map(|slot| save_file::load(save_slot::slot_path(profile_path, slot))) {
+            Some(Ok((game_state, restored_from))) => {
+                self.restored_from_backup_message(&game_state, restored_from);
+                (Some(game_state), None)
+            }
+            None | Some(Err(save_file::SaveFileError::MissingFile)) => (None, None),
+            Some(Err(error)) => (None, Some(error)),
+        };
         let mut current_menu_state = None;
 
         loop {
 
             self.renderer.borrow_mut().reset_buffers();
 
-            let mut control_map = control_file::from_file(args.user_path.join(user_files::CONTROL)).unwrap_or_default();
+            let mut control_map = control_file::from_file(profile_path.join(user_files::CONTROL)).unwrap_or_default();
 
             let mut menu = SelectMenu::new();
 
@@ -162,7 +321,14 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
             }
 
             menu.push(SelectMenuItem::new(MenuMessageType::NewGame, MainMenuSelection::NewGame));
+            menu.push(SelectMenuItem::new(MenuMessageType::DailyChallenge, MainMenuSelection::DailyChallenge));
+
+            if save_slot::is_any_occupied(profile_path) {
+                menu.push(SelectMenuItem::new(MenuMessageType::LoadGame, MainMenuSelection::LoadGame));
+            }
+
             menu.push(SelectMenuItem::new(MenuMessageType::Controls, MainMenuSelection::Controls));
+            menu.push(SelectMenuItem::new(MenuMessageType::HighScores, MainMenuSelection::HighScores));
 
             if current_game_state.is_some() {
                 menu.push(SelectMenuItem::new(MenuMessageType::SaveAndQuit, MainMenuSelection::SaveAndQuit));
@@ -176,7 +342,7 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
                 let menu_op = SelectMenuOperation::new(
                     renderer,
                     &mut self.input_source,
-                    Some(MessageType::Title),
+                    Some(load_error.map_or(MessageType::Title, MessageType::SaveLoadFailed)),
                     &self.language,
                     menu,
                     current_menu_state);
@@ -196,52 +362,182 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
                 }
                 MainMenuSelection::SaveAndQuit => {
                     let game_state = current_game_state.take().expect("Missing game state");
-                    save_file::save(args.user_path.as_path(), game_state);
+                    save_slot::save(profile_path, current_slot.expect("Missing save slot"), game_state).1?;
                     return Ok(());
                 }
                 MainMenuSelection::NewGame => {
-                    let mut game_state = GameState::new();
+                    let slot = if let Some(slot) = self.choose_save_slot(profile_path, false) {
+                        slot
+                    } else {
+                        current_menu_state = Some(menu_state);
+                        continue;
+                    };
+
+                    // keep the paused game before switching slots
+                    if let Some(game_state) = current_game_state.take() {
+                        save_slot::save(profile_path, current_slot.expect("Missing save slot"), game_state).1?;
+                    }
+
+                    // any unloadable save is no longer current
+                    load_error = None;
+                    current_slot = Some(slot);
+
+                    self.new_game(GameMode::Normal)
+                }
+                MainMenuSelection::DailyChallenge => {
+                    let day = self.daily_challenge_day.unwrap_or_else(daily_challenge_day);
+                    let mut leaderboard = daily_file::load(&args.shared_user_path).unwrap_or_default();
+
+                    if leaderboard.has_attempted(day, &args.profile) {
+                        self.daily_leaderboard(&leaderboard, day);
+                        current_menu_state = Some(menu_state);
+                        continue;
+                    }
+
+                    let slot = if let Some(slot) = self.choose_save_slot(profile_path, false) {
+                        slot
+                    } else {
+                        current_menu_state = Some(menu_state);
+                        continue;
+                    };
 
-                    self.init_demo(&mut game_state);
-                    self.intro_message();
-                    self.welcome_message(&game_state);
+                    if let Some(game_state) = current_game_state.take() {
+                        save_slot::save(profile_path, current_slot.expect("Missing save slot"), game_state).1?;
+                    }
+
+                    // the attempt is used up as soon as the game starts
+                    leaderboard.record_attempt(day, &args.profile);
+                    let _ = daily_file::save(&args.shared_user_path, &leaderboard);
+
+                    load_error = None;
+                    current_slot = Some(slot);
 
-                    game_state
+                    self.new_game(GameMode::Daily(day))
+                }
+                MainMenuSelection::LoadGame => {
+                    let slot = if let Some(slot) = self.choose_save_slot(profile_path, true) {
+                        slot
+                    } else {
+                        current_menu_state = Some(menu_state);
+                        continue;
+                    };
+
+                    if let Some(game_state) = current_game_state.take() {
+                        save_slot::save(profile_path, current_slot.expect("Missing save slot"), game_state).1?;
+                    }
+
+                    current_slot = Some(slot);
+
+                    match save_file::load(save_slot::slot_path(profile_path, slot)) {
+                        Ok((game_state, restored_from)) => {
+                            self.restored_from_backup_message(&game_state, restored_from);
+                            load_error = None;
+                            game_state
+                        }
+                        Err(error) => {
+                            load_error = Some(error);
+                            continue;
+                        }
+                    }
                 }
                 MainMenuSelection::Continue => current_game_state.take().expect("Missing game state"),
                 MainMenuSelection::Controls => {
-                    self.configure_controls(&mut control_map);
-                    control_file::to_file(args.user_path.join(user_files::CONTROL), &control_map);
+                    self.configure_controls(&mut control_map, args.debug);
+                    control_file::to_file(profile_path.join(user_files::CONTROL), &control_map);
+                    current_menu_state = Some(menu_state);
+                    continue;
+                }
+                MainMenuSelection::HighScores => {
+                    self.high_scores(profile_path);
                     current_menu_state = Some(menu_state);
                     continue;
                 }
             };
 
+            if args.debug {
+                control_map.add_debug_defaults();
+            } else {
+                control_map.remove_debug_controls();
+            }
+
             Self::install_control_map(&mut game_state, control_map);
 
-            match self.game_loop(&mut game_state)? {
+            self.autosave.set_slot(profile_path, current_slot.expect("Missing save slot"), &game_state);
+            let exit_reason = self.game_loop(&mut game_state)?;
+            self.autosave.clear_slot();
+
+            match exit_reason {
                 ExitReason::Pause => {
                     current_game_state = Some(game_state);
                 }
                 ExitReason::Quit => {
-                    save_file::save(args.user_path.as_path(), game_state);
+                    save_slot::save(profile_path, current_slot.expect("Missing save slot"), game_state).1?;
                     return Ok(());
                 }
                 ExitReason::GameOver(reason) => {
                     match reason {
                         GameOverReason::PlayerDied => {
+                            // recorded first so any problem with the history is shown with the death message
+                            self.record_run(&args, RunOutcome::Died, &game_state);
                             self.death_message(&game_state);
+                            self.write_morgue(profile_path, &game_state);
                             self.input_source.next_input();
                         }
                     }
                     current_game_state = None;
-                    save_file::delete(args.user_path.as_path());
+                    if let Some(slot) = current_slot.take() {
+                        save_slot::delete(profile_path, slot);
+                    }
                 }
             }
 
         }
     }
 
+    /// Lets the player pick a save slot, showing the game saved in each.
+    /// Returns None if the player backs out.
+    fn choose_save_slot(&mut self, profile_path: &Path, occupied_only: bool) -> Option<usize> {
+        let mut menu = SelectMenu::new();
+
+        for slot in save_slot::slots() {
+            let summary = save_slot::summary(profile_path, slot);
+            if occupied_only && summary == save_slot::SaveSlotSummary::Empty {
+                continue;
+            }
+            menu.push(SelectMenuItem::new(MenuMessageType::SaveSlot(slot, summary), slot));
+        }
+
+        let mut renderer_borrow = self.renderer.borrow_mut();
+        let renderer = renderer_borrow.deref_mut();
+        SelectMenuOperation::new(
+            renderer,
+            &mut self.input_source,
+            Some(MessageType::ChooseSaveSlot),
+            &self.language,
+            menu,
+            None).run_can_escape().map(|(slot, _)| slot)
+    }
+
+    /// Plays a new game without the main menu or intro, with the player
+    /// controlled by the given behaviour. Used to run games without a frontend.
+    pub fn play_new_game(&mut self, pc_behaviour: BehaviourType) -> GameResult<(ExitReason, GameState)> {
+        let seed = self.next_game_seed();
+        self.rng.reseed(seed);
+        let mut game_state = GameState::new_with_mode(GameMode::Normal, seed);
+
+        self.init_demo(&mut game_state);
+        Self::install_control_map(&mut game_state, ControlMap::default());
+
+        {
+            let GlobalIds { pc_id, level_id } = game_state.global_ids.expect("Uninitialised game state");
+            game_state.levels.level_mut(level_id).ecs.insert_behaviour_type(pc_id, pc_behaviour);
+        }
+
+        let reason = self.game_loop(&mut game_state)?;
+
+        Ok((reason, game_state))
+    }
+
     fn install_control_map(game_state: &mut GameState, control_map: ControlMap) {
         let GlobalIds { pc_id, level_id } = game_state.global_ids.expect("Uninitialised game state");
 
@@ -250,6 +546,12 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
     }
 
     fn game_loop(&mut self, game_state: &mut GameState) -> GameResult<ExitReason> {
+
+        // the game state may have changed since the journal was last used
+        self.journal.clear();
+
+        self.rules = RuleRegistry::for_mode(game_state.mode(), &self.rules_config);
+
         loop {
 
             let GlobalIds { pc_id, level_id } = game_state.global_ids.expect("Uninitialised game state");
@@ -270,14 +572,21 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
                         ecs: &mut level.ecs,
                         spatial_hash: &mut level.spatial_hash,
                         behaviour_ctx: &self.behaviour_ctx,
+                        rules: &self.rules,
+                        rule_trace: &mut self.rule_trace,
+                        event_bus: &mut self.event_bus,
                         rule_reactions: &mut self.rule_reactions,
                         ecs_action: &mut self.ecs_action,
                         action_schedule: &mut self.action_schedule,
                         turn_schedule: &mut level.turn_schedule,
                         pc_observer: &self.pc_observer,
                         entity_ids: &game_state.entity_ids,
+                        stats: &mut game_state.stats,
                         rng: &self.rng,
                         language: &self.language,
+                        clock: &self.clock,
+                        journal: &mut self.journal,
+                        state_trace: &mut self.state_trace,
                     }.turn()?
 
                 } else {
@@ -294,16 +603,27 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
                     return Ok(reason);
                 }
                 TurnResolution::Schedule(entity_id, delay) => {
-                    let level = game_state.levels.level_mut(level_id);
-                    let ticket = level.turn_schedule.insert(entity_id, delay);
-                    level.ecs.insert_schedule_ticket(entity_id, ticket);
+                    {
+                        let level = game_state.levels.level_mut(level_id);
+                        self.journal.record_schedule_ticket(&level.ecs, entity_id);
+                        let ticket = level.turn_schedule.insert(entity_id, delay);
+                        level.ecs.insert_schedule_ticket(entity_id, ticket);
+                    }
+
+                    if entity_id == pc_id {
+                        if let Err(error) = self.autosave.save_if_due(game_state) {
+                            self.add_message(game_state, MessageType::AutosaveFailed(error));
+                        }
+                    }
                 }
                 TurnResolution::LevelSwitch { entity_id, exit_id, level_switch } => {
+                    self.journal.clear();
                     self.switch_level(entity_id, exit_id, level_switch, game_state);
                 }
                 TurnResolution::GameOver(reason) => {
                     return Ok(ExitReason::GameOver(reason));
                 }
+                TurnResolution::Removed(_) => {}
             }
         }
     }
@@ -324,6 +644,19 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
             &RenderOverlay::Death);
     }
 
+    fn write_morgue(&self, user_path: &Path, game_state: &GameState) {
+        let num_messages = self.renderer.borrow().fullscreen_log_num_rows();
+
+        // the game is already over, so failing to write the morgue file isn't fatal
+        let _ = morgue_file::write(user_path, game_state, num_messages, &self.language);
+    }
+
+    fn restored_from_backup_message(&self, game_state: &GameState, restored_from: Option<save_file::SaveFileError>) {
+        if let Some(error) = restored_from {
+            self.add_message(game_state, MessageType::RestoredFromBackup(error));
+        }
+    }
+
     fn add_message(&self, game_state: &GameState, message: MessageType) {
         let GlobalIds { pc_id, level_id } = game_state.global_ids.expect("Unitialised game state");
 
@@ -331,7 +664,9 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
         ecs.message_log_borrow_mut(pc_id).expect("Expected message log component").add(message);
     }
 
-    fn configure_controls(&mut self, control_map: &mut ControlMap) {
+    /// Lets the player rebind controls. Debug controls are only listed in
+    /// debug mode.
+    fn configure_controls(&mut self, control_map: &mut ControlMap, debug: bool) {
 
         let mut renderer_borrow = self.renderer.borrow_mut();
         let mut renderer = renderer_borrow.deref_mut();
@@ -341,7 +676,7 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
             let mut menu = SelectMenu::new();
             let descriptions = control_map.descriptions();
 
-            for (control, maybe_input) in descriptions.iter() {
+            for (control, maybe_input) in descriptions.iter().filter(|&(c, _)| debug || !c.is_debug()) {
                 let message = if let Some(input) = maybe_input {
                     MenuMessageType::Control(input, control)
                 } else {
@@ -362,7 +697,7 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
                 current_menu_state = Some(menu_state.clone());
                 let mut menu = SelectMenu::new();
 
-                for (control, maybe_input) in descriptions.iter() {
+                for (control, maybe_input) in descriptions.iter().filter(|&(c, _)| debug || !c.is_debug()) {
                     let message = if control == control_to_change {
                         MenuMessageType::ControlBinding(control)
                     } else {
@@ -396,6 +731,117 @@ impl<Renderer: KnowledgeRenderer, Input: 'static + InputSource + Clone> GameCtx<
         }
     }
 
+    /// The first normal game in a session uses the session's seed, so it can
+    /// be reproduced by passing the same seed on the command line. Each later
+    /// game's seed is derived from the one before it.
+    fn next_game_seed(&mut self) -> usize {
+        let seed = self.next_seed;
+        self.next_seed = GameRng::new(seed).gen_usize();
+        seed
+    }
+
+    fn new_game(&mut self, mode: GameMode) -> GameState {
+        let seed = match mode {
+            GameMode::Normal => self.next_game_seed(),
+            GameMode::Daily(day) => daily_challenge_seed(day),
+        };
+
+        // every game starts from its own seed, so the levels of one game
+        // don't depend on the games played before it in the session
+        self.rng.reseed(seed);
+
+        let mut game_state = GameState::new_with_mode(mode, seed);
+
+        self.init_demo(&mut game_state);
+        self.intro_message();
+        self.welcome_message(&game_state);
+
+        game_state
+    }
+
+    /// Adds a finished game to the run history, or to the daily leaderboard
+    /// if it was a daily challenge
+    fn record_run(&self, args: &Arguments, outcome: RunOutcome, game_state: &GameState) {
+        // failing to record a finished game isn't fatal
+        let record = RunRecord::new(game_state.seed(), outcome, game_state);
+
+        match game_state.mode() {
+            GameMode::Normal => {
+                if let Ok(Some(_)) = run_history_file::append(&args.user_path, record) {
+                    self.add_message(game_state, MessageType::RunHistoryCorrupt);
+                }
+            }
+            GameMode::Daily(day) => {
+                if let Ok(mut leaderboard) = daily_file::load(&args.shared_user_path) {
+                    leaderboard.push_result(DailyResult {
+                        day: day,
+                        profile: args.profile.clone(),
+                        record: record,
+                    });
+                    let _ = daily_file::save(&args.shared_user_path, &leaderboard);
+                }
+            }
+        }
+    }
+
+    fn daily_leaderboard(&mut self, leaderboard: &DailyLeaderboard, day: u64) {
+        let mut message = Message::new();
+
+        self.language.translate(MessageType::DailyLeaderboard(day), &mut message);
+        message.push(MessagePart::Newline);
+        message.push(MessagePart::Newline);
+        self.language.translate(MessageType::DailyAlreadyAttempted, &mut message);
+        message.push(MessagePart::Newline);
+        message.push(MessagePart::Newline);
+
+        let results = leaderboard.results_for_day(day);
+        if results.is_empty() {
+            self.language.translate(MessageType::NoRuns, &mut message);
+            message.push(MessagePart::Newline);
+        }
+
+        for (index, result) in results.into_iter().enumerate() {
+            self.language.translate_daily_result(index + 1, result, &mut message);
+            message.push(MessagePart::Newline);
+        }
+
+        message.push(MessagePart::Newline);
+        self.language.translate(MessageType::PressAnyKey, &mut message);
+
+        display_message_scrolling(self.renderer.borrow_mut().deref_mut(), &mut self.input_source, &message, true);
+    }
+
+    fn high_scores(&mut self, user_path: &Path) {
+        let (history, readable) = match run_history_file::load(user_path) {
+            Ok(history) => (history, true),
+            Err(_) => (RunHistory::new(), false),
+        };
+
+        let mut message = Message::new();
+
+        self.language.translate(MessageType::HighScores, &mut message);
+        message.push(MessagePart::Newline);
+        message.push(MessagePart::Newline);
+
+        if !readable {
+            self.language.translate(MessageType::RunHistoryUnreadable, &mut message);
+            message.push(MessagePart::Newline);
+        } else if history.is_empty() {
+            self.language.translate(MessageType::NoRuns, &mut message);
+            message.push(MessagePart::Newline);
+        }
+
+        for (index, record) in history.high_scores().into_iter().enumerate() {
+            self.language.translate_run(index + 1, record, &mut message);
+            message.push(MessagePart::Newline);
+        }
+
+        message.push(MessagePart::Newline);
+        self.language.translate(MessageType::PressAnyKey, &mut message);
+
+        display_message_scrolling(self.renderer.borrow_mut().deref_mut(), &mut self.input_source, &message, true);
+    }
+
     fn intro_message(&mut self) {
 
         let mut message = Message::new();
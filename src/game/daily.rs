@@ -0,0 +1,66 @@
+use std::hash::Hasher;
+
+use game::*;
+use util::StableHasher;
+
+const SECONDS_PER_DAY: u64 = 86400;
+
+/// The day of the current daily challenge, counted from the unix epoch. A
+/// new challenge begins at midnight UTC.
+pub fn daily_challenge_day() -> u64 {
+    unix_now() / SECONDS_PER_DAY
+}
+
+pub fn daily_challenge_date(day: u64) -> UtcDateTime {
+    UtcDateTime::from_unix(day * SECONDS_PER_DAY)
+}
+
+/// Every player gets the same seed for a given day's challenge
+pub fn daily_challenge_seed(day: u64) -> usize {
+    let mut hasher = StableHasher::new();
+    hasher.write(b"daily");
+    hasher.write_u64(day);
+    hasher.finish() as usize
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub day: u64,
+    pub profile: String,
+    pub record: RunRecord,
+}
+
+/// Results of the daily challenges played by every profile. Each profile
+/// gets one attempt per day, which is used up as soon as the game starts.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DailyLeaderboard {
+    attempts: Vec<(u64, String)>,
+    results: Vec<DailyResult>,
+}
+
+impl DailyLeaderboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn has_attempted(&self, day: u64, profile: &str) -> bool {
+        self.attempts.iter().any(|&(d, ref p)| d == day && p == profile)
+    }
+
+    pub fn record_attempt(&mut self, day: u64, profile: &str) {
+        if !self.has_attempted(day, profile) {
+            self.attempts.push((day, profile.to_string()));
+        }
+    }
+
+    pub fn push_result(&mut self, result: DailyResult) {
+        self.results.push(result);
+    }
+
+    /// Results for a day, ordered from highest to lowest score
+    pub fn results_for_day(&self, day: u64) -> Vec<&DailyResult> {
+        let mut results: Vec<&DailyResult> = self.results.iter().filter(|r| r.day == day).collect();
+        results.sort_by(|a, b| b.record.score.cmp(&a.record.score));
+        results
+    }
+}
@@ -0,0 +1,16 @@
+use std::path::Path;
+
+use game::*;
+use game::game_file::{FileError, FileResult};
+
+/// Returns an empty leaderboard if no daily challenges have been played
+pub fn load<P: AsRef<Path>>(user_path: P) -> FileResult<DailyLeaderboard> {
+    match game_file::read_json(user_path.as_ref().join(user_files::DAILY)) {
+        Err(FileError::MissingFile) => Ok(DailyLeaderboard::new()),
+        other => other,
+    }
+}
+
+pub fn save<P: AsRef<Path>>(user_path: P, leaderboard: &DailyLeaderboard) -> FileResult<()> {
+    game_file::write_json(user_path.as_ref().join(user_files::DAILY), leaderboard)
+}
@@ -0,0 +1,29 @@
+/// Running totals of what a character has done, updated as actions are
+/// committed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CharacterStats {
+    pub kills: u64,
+    pub damage_dealt: u64,
+    pub damage_taken: u64,
+    pub bullets_fired: u64,
+    pub bullets_hit: u64,
+    pub doors_opened: u64,
+    pub tiles_explored: u64,
+    /// Number of times the character moved between levels
+    pub levels_entered: u64,
+}
+
+impl CharacterStats {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Fraction of bullets fired which hit something with hit points
+    pub fn accuracy(&self) -> Option<f64> {
+        if self.bullets_fired == 0 {
+            None
+        } else {
+            Some(self.bullets_hit as f64 / self.bullets_fired as f64)
+        }
+    }
+}
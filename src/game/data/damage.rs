@@ -0,0 +1,128 @@
+use std::cmp;
+
+use ecs::EntityId;
+
+/// What an attack is made of, which determines what it can hurt
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DamageType {
+    Physical,
+    Silver,
+    /// Damage from the other side of the tear
+    Otherworldly,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Damage {
+    pub amount: usize,
+    pub damage_type: DamageType,
+}
+
+impl Damage {
+    pub fn new(amount: usize, damage_type: DamageType) -> Self {
+        Damage {
+            amount: amount,
+            damage_type: damage_type,
+        }
+    }
+
+    pub fn physical(amount: usize) -> Self {
+        Self::new(amount, DamageType::Physical)
+    }
+
+    /// The same type of damage, with its amount multiplied
+    pub fn multiplied(self, multiplier: usize) -> Self {
+        Self::new(self.amount * multiplier, self.damage_type)
+    }
+
+    /// The amount of damage taken by an entity with the given armour and
+    /// resistances. Armour only absorbs physical damage, and never reduces
+    /// the damage of a hit below 1.
+    pub fn amount_taken(self, armour: usize, resistances: Resistances) -> usize {
+        let amount = resistances.get(self.damage_type).modify(self.amount);
+
+        if self.damage_type == DamageType::Physical && amount > 0 {
+            cmp::max(amount.saturating_sub(armour), 1)
+        } else {
+            amount
+        }
+    }
+}
+
+/// What dealt the damage in an action
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DamageSource {
+    /// An attacker, a projectile, or a character that was knocked into
+    Entity(EntityId),
+    Bleeding,
+    Explosion,
+    /// Being knocked into something
+    Impact,
+}
+
+/// How strongly an entity is affected by a type of damage
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Resistance {
+    Immune,
+    /// Takes half damage, rounded up
+    Resistant,
+    Normal,
+    /// Takes double damage
+    Vulnerable,
+}
+
+impl Resistance {
+    pub fn modify(self, amount: usize) -> usize {
+        match self {
+            Resistance::Immune => 0,
+            Resistance::Resistant => (amount + 1) / 2,
+            Resistance::Normal => amount,
+            Resistance::Vulnerable => amount * 2,
+        }
+    }
+}
+
+/// An entity's resistance to each type of damage
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Resistances {
+    physical: Resistance,
+    silver: Resistance,
+    otherworldly: Resistance,
+}
+
+impl Default for Resistances {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Resistances {
+    /// Normal resistance to every type of damage
+    pub fn new() -> Self {
+        Resistances {
+            physical: Resistance::Normal,
+            silver: Resistance::Normal,
+            otherworldly: Resistance::Normal,
+        }
+    }
+
+    pub fn with(mut self, damage_type: DamageType, resistance: Resistance) -> Self {
+        *self.get_mut(damage_type) = resistance;
+        self
+    }
+
+    pub fn get(&self, damage_type: DamageType) -> Resistance {
+        match damage_type {
+            DamageType::Physical => self.physical,
+            DamageType::Silver => self.silver,
+            DamageType::Otherworldly => self.otherworldly,
+        }
+    }
+
+    fn get_mut(&mut self, damage_type: DamageType) -> &mut Resistance {
+        match damage_type {
+            DamageType::Physical => &mut self.physical,
+            DamageType::Silver => &mut self.silver,
+            DamageType::Otherworldly => &mut self.otherworldly,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use game::data::Damage;
+
+/// Damage dealt to everything within range of an explosion
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Explosive {
+    pub radius: usize,
+    pub damage: Damage,
+}
+
+impl Explosive {
+    pub fn new(radius: usize, damage: Damage) -> Self {
+        Explosive {
+            radius: radius,
+            damage: damage,
+        }
+    }
+}
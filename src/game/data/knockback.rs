@@ -0,0 +1,33 @@
+use ecs::EntityId;
+use direction::Direction;
+use game::data::{Damage, DamageType};
+
+/// Damage taken by an entity which is knocked into something solid
+pub const KNOCKBACK_IMPACT_DAMAGE: Damage = Damage {
+    amount: 1,
+    damage_type: DamageType::Physical,
+};
+
+/// Number of turns an entity is stunned for after being knocked into
+/// something solid
+pub const KNOCKBACK_IMPACT_STUN_DURATION: usize = 2;
+
+/// An entity being pushed some number of cells in a direction, one cell at a
+/// time
+#[derive(Clone, Copy, Debug)]
+pub struct Knockback {
+    pub entity_id: EntityId,
+    pub direction: Direction,
+    /// Number of cells the entity is still to be pushed
+    pub distance: usize,
+}
+
+impl Knockback {
+    pub fn new(entity_id: EntityId, direction: Direction, distance: usize) -> Self {
+        Knockback {
+            entity_id: entity_id,
+            direction: direction,
+            distance: distance,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::cmp;
+
+/// Rounds loaded into a firearm, and spare rounds carried for reloading it.
+/// Spare rounds are never replenished, so running out of ammunition is
+/// part of the game rather than something to be fixed by finding more.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Magazine {
+    loaded: usize,
+    capacity: usize,
+    reserve: usize,
+}
+
+impl Magazine {
+    /// A full magazine, with a number of spare rounds
+    pub fn new(capacity: usize, reserve: usize) -> Self {
+        Magazine {
+            loaded: capacity,
+            capacity: capacity,
+            reserve: reserve,
+        }
+    }
+
+    pub fn loaded(&self) -> usize {
+        self.loaded
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn reserve(&self) -> usize {
+        self.reserve
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.loaded == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.loaded >= self.capacity
+    }
+
+    /// Removes a round from the magazine. Returns false if it was empty.
+    pub fn fire(&mut self) -> bool {
+        if self.loaded == 0 {
+            return false;
+        }
+
+        self.loaded -= 1;
+        true
+    }
+
+    /// Fills the magazine from the spare rounds, returning the number of
+    /// rounds loaded
+    pub fn reload(&mut self) -> usize {
+        let amount = cmp::min(self.capacity - self.loaded, self.reserve);
+        self.loaded += amount;
+        self.reserve -= amount;
+        amount
+    }
+}
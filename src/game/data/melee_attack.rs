@@ -0,0 +1,32 @@
+use game::data::Damage;
+
+/// Damage dealt by a critical hit is multiplied by this amount
+pub const CRITICAL_HIT_MULTIPLIER: usize = 2;
+
+/// How an entity attacks what it walks into
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct MeleeAttack {
+    pub damage: Damage,
+    /// Probability of hitting a target with no evasion
+    pub accuracy: f64,
+    /// Probability that a hit is a critical hit
+    pub critical_chance: f64,
+    /// Number of cells a critical hit pushes the target away from the attacker
+    pub knockback: usize,
+}
+
+impl MeleeAttack {
+    pub fn new(damage: Damage, accuracy: f64, critical_chance: f64, knockback: usize) -> Self {
+        MeleeAttack {
+            damage: damage,
+            accuracy: accuracy,
+            critical_chance: critical_chance,
+            knockback: knockback,
+        }
+    }
+
+    /// Probability of hitting a target with a given evasion
+    pub fn hit_chance(&self, evasion: f64) -> f64 {
+        self.accuracy * (1.0 - evasion)
+    }
+}
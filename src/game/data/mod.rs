@@ -8,6 +8,14 @@ mod action_description;
 mod level_switch;
 mod projectile_collision;
 mod hit_points;
+mod character_stats;
+mod damage;
+mod melee_attack;
+mod magazine;
+mod projectile_type;
+mod explosive;
+mod knockback;
+mod status_effects;
 
 pub use self::door_state::*;
 pub use self::close::*;
@@ -19,3 +27,14 @@ pub use self::action_description::*;
 pub use self::level_switch::*;
 pub use self::projectile_collision::*;
 pub use self::hit_points::*;
+pub use self::character_stats::*;
+pub use self::damage::*;
+pub use self::melee_attack::*;
+pub use self::magazine::*;
+pub use self::projectile_type::*;
+pub use self::explosive::*;
+pub use self::knockback::*;
+pub use self::status_effects::*;
+
+#[cfg(test)]
+mod tests;
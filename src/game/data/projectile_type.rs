@@ -0,0 +1,27 @@
+/// The kinds of projectile which can be fired
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProjectileType {
+    /// A single bullet, destroyed on its first collision
+    Bullet,
+    /// Several pellets fired in a fan
+    Spread,
+    /// A round which passes through the entities it hits
+    Piercing,
+    /// A round which explodes on collision
+    Explosive,
+    /// A bullet which harms creatures from the other side of the tear
+    Silver,
+}
+
+impl ProjectileType {
+    /// The projectile type following this one when cycling through types
+    pub fn next(self) -> Self {
+        match self {
+            ProjectileType::Bullet => ProjectileType::Spread,
+            ProjectileType::Spread => ProjectileType::Piercing,
+            ProjectileType::Piercing => ProjectileType::Explosive,
+            ProjectileType::Explosive => ProjectileType::Silver,
+            ProjectileType::Silver => ProjectileType::Bullet,
+        }
+    }
+}
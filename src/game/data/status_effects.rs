@@ -0,0 +1,145 @@
+use std::cmp;
+
+use ecs::EntityId;
+use game::data::{Damage, DamageType};
+
+pub const NUM_STATUS_EFFECTS: usize = 4;
+
+/// Damage taken by a bleeding entity at the end of each of its turns
+pub const BLEEDING_DAMAGE: Damage = Damage {
+    amount: 1,
+    damage_type: DamageType::Physical,
+};
+
+/// Kinds of temporary effect on an entity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatusEffectType {
+    /// Loses a hit point at the end of each turn
+    Bleeding,
+    /// Loses each turn, and can see half as far
+    Stunned,
+    /// Takes twice as long to act
+    Slowed,
+    /// Won't make melee attacks
+    Terrified,
+}
+
+pub const STATUS_EFFECT_TYPES: [StatusEffectType; NUM_STATUS_EFFECTS] = [
+    StatusEffectType::Bleeding,
+    StatusEffectType::Stunned,
+    StatusEffectType::Slowed,
+    StatusEffectType::Terrified,
+];
+
+impl StatusEffectType {
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Name shown in the HUD while the effect is active
+    pub fn short_name(self) -> &'static str {
+        match self {
+            StatusEffectType::Bleeding => "Bleed",
+            StatusEffectType::Stunned => "Stun",
+            StatusEffectType::Slowed => "Slow",
+            StatusEffectType::Terrified => "Fear",
+        }
+    }
+}
+
+/// An effect lasting for a number of the affected entity's turns
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEffect {
+    pub effect_type: StatusEffectType,
+    pub duration: usize,
+}
+
+impl StatusEffect {
+    pub fn new(effect_type: StatusEffectType, duration: usize) -> Self {
+        StatusEffect {
+            effect_type: effect_type,
+            duration: duration,
+        }
+    }
+}
+
+/// A status effect to be applied to an entity
+#[derive(Clone, Copy, Debug)]
+pub struct StatusEffectInfliction {
+    pub entity_id: EntityId,
+    pub effect: StatusEffect,
+}
+
+impl StatusEffectInfliction {
+    pub fn new(entity_id: EntityId, effect: StatusEffect) -> Self {
+        StatusEffectInfliction {
+            entity_id: entity_id,
+            effect: effect,
+        }
+    }
+}
+
+/// The number of turns remaining of each status effect on an entity. An
+/// effect with no turns remaining is inactive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusEffects {
+    durations: [usize; NUM_STATUS_EFFECTS],
+}
+
+impl StatusEffects {
+    pub fn new() -> Self {
+        StatusEffects {
+            durations: [0; NUM_STATUS_EFFECTS],
+        }
+    }
+
+    pub fn duration(&self, effect_type: StatusEffectType) -> usize {
+        self.durations[effect_type.index()]
+    }
+
+    pub fn is_active(&self, effect_type: StatusEffectType) -> bool {
+        self.duration(effect_type) > 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.durations.iter().all(|&duration| duration == 0)
+    }
+
+    /// Iterates over the active effects
+    pub fn iter<'a>(&'a self) -> Box<Iterator<Item=StatusEffect> + 'a> {
+        Box::new(STATUS_EFFECT_TYPES.iter()
+                 .filter(move |effect_type| self.is_active(**effect_type))
+                 .map(move |effect_type| StatusEffect::new(*effect_type, self.duration(*effect_type))))
+    }
+
+    /// Applies an effect, extending it if it is already active
+    pub fn apply(&mut self, effect: StatusEffect) {
+        let duration = &mut self.durations[effect.effect_type.index()];
+        *duration = cmp::max(*duration, effect.duration);
+    }
+
+    /// Reduces the duration of each active effect by a turn
+    pub fn tick(&mut self) {
+        for duration in self.durations.iter_mut() {
+            if *duration > 0 {
+                *duration -= 1;
+            }
+        }
+    }
+
+    pub fn modify_turn_time(&self, turn_time: u64) -> u64 {
+        if self.is_active(StatusEffectType::Slowed) {
+            turn_time * 2
+        } else {
+            turn_time
+        }
+    }
+
+    pub fn modify_vision_distance(&self, vision_distance: usize) -> usize {
+        if self.is_active(StatusEffectType::Stunned) {
+            vision_distance / 2
+        } else {
+            vision_distance
+        }
+    }
+}
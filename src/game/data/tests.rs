@@ -0,0 +1,45 @@
+use game::data::*;
+
+/// The resistances of a terror pillar's shadow form
+fn shadow_form() -> Resistances {
+    Resistances::new()
+        .with(DamageType::Physical, Resistance::Immune)
+        .with(DamageType::Silver, Resistance::Vulnerable)
+        .with(DamageType::Otherworldly, Resistance::Immune)
+}
+
+#[test]
+fn armour_only_absorbs_physical_damage() {
+    assert_eq!(Damage::physical(3).amount_taken(1, Resistances::new()), 2);
+    assert_eq!(Damage::new(3, DamageType::Silver).amount_taken(1, Resistances::new()), 3);
+    assert_eq!(Damage::new(3, DamageType::Otherworldly).amount_taken(1, Resistances::new()), 3);
+}
+
+#[test]
+fn armour_leaves_at_least_one_damage() {
+    assert_eq!(Damage::physical(1).amount_taken(1, Resistances::new()), 1);
+    assert_eq!(Damage::physical(2).amount_taken(5, Resistances::new()), 1);
+}
+
+#[test]
+fn shadow_form_is_immune_to_bullets() {
+    assert_eq!(Damage::physical(2).amount_taken(0, shadow_form()), 0);
+    assert_eq!(Damage::new(2, DamageType::Otherworldly).amount_taken(0, shadow_form()), 0);
+}
+
+#[test]
+fn silver_hurts_the_shadow_form_more() {
+    let resistances = shadow_form();
+
+    let mut hit_points = HitPoints::new(3);
+    hit_points.dec(Damage::new(1, DamageType::Silver).amount_taken(0, resistances));
+    assert_eq!(hit_points.current(), 1);
+}
+
+#[test]
+fn resistance_halves_damage_rounding_up() {
+    let resistances = Resistances::new().with(DamageType::Physical, Resistance::Resistant);
+
+    assert_eq!(Damage::physical(3).amount_taken(0, resistances), 2);
+    assert_eq!(Damage::physical(4).amount_taken(0, resistances), 2);
+}
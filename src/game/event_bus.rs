@@ -0,0 +1,161 @@
+use game::*;
+use game::data::*;
+use ecs::*;
+use coord::Coord;
+
+/// A change made to a level by committing an action
+#[derive(Debug, Clone, Copy)]
+pub enum GameEvent {
+    /// `from` is None if the entity had no position before the action
+    Moved {
+        entity_id: EntityId,
+        from: Option<Coord>,
+        to: Coord,
+    },
+    /// `source` is None if the entity's hit points were lowered by
+    /// something other than damage
+    Damaged {
+        entity_id: EntityId,
+        amount: usize,
+        hit_points: HitPoints,
+        source: Option<DamageSource>,
+    },
+    Died(EntityId),
+    ProjectileFired(EntityId),
+    ProjectileHit(ProjectileCollision),
+    DoorOpened(EntityId),
+    LevelSwitch(LevelSwitchAction),
+}
+
+/// Everything that changed when an action was committed
+#[derive(Debug, Clone)]
+pub struct CommitSummary {
+    pub action_id: ActionId,
+    pub level_id: LevelId,
+    /// The entity whose turn it was when the action was committed
+    pub entity_id: EntityId,
+    pub events: Vec<GameEvent>,
+}
+
+/// Implemented by anything which reacts to changes in the game, without
+/// being part of the rules which decide what those changes are
+pub trait CommitListener {
+    /// Called after an action has been committed. The entity store reflects
+    /// the state of the level after the action.
+    fn notify(&mut self, summary: &CommitSummary, ecs: &EcsCtx);
+}
+
+/// Passes a summary of each committed action to every subscribed listener
+pub struct EventBus {
+    listeners: Vec<Box<CommitListener>>,
+    summary: CommitSummary,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        EventBus {
+            listeners: Vec::new(),
+            summary: CommitSummary {
+                action_id: 0,
+                level_id: 0,
+                entity_id: 0,
+                events: Vec::new(),
+            },
+        }
+    }
+
+    pub fn subscribe(&mut self, listener: Box<CommitListener>) {
+        self.listeners.push(listener);
+    }
+
+    /// The changes found by the last call to `summarise`
+    pub fn summary(&self) -> &CommitSummary {
+        &self.summary
+    }
+
+    /// Works out what an action will change. Must be called before the
+    /// action is committed, as the changes are found by comparing the action
+    /// to the current contents of the entity store.
+    pub fn summarise(&mut self,
+                     action: &EcsAction,
+                     ecs: &EcsCtx,
+                     action_id: ActionId,
+                     level_id: LevelId,
+                     entity_id: EntityId,
+                     pc_id: EntityId) {
+
+        self.summary.action_id = action_id;
+        self.summary.level_id = level_id;
+        self.summary.entity_id = entity_id;
+        self.summary.events.clear();
+
+        for (entity_id, to) in action.position_profile().insertion_copy_iter() {
+            let from = ecs.position(entity_id);
+            if from != Some(to) {
+                self.summary.events.push(GameEvent::Moved {
+                    entity_id: entity_id,
+                    from: from,
+                    to: to,
+                });
+            }
+        }
+
+        for (entity_id, hit_points) in action.hit_points_profile().insertion_copy_iter() {
+            if let Some(current) = ecs.hit_points(entity_id) {
+                if hit_points.current() < current.current() {
+                    self.summary.events.push(GameEvent::Damaged {
+                        entity_id: entity_id,
+                        amount: (current.current() - hit_points.current()) as usize,
+                        hit_points: hit_points,
+                        source: action.damage_source(),
+                    });
+                }
+            }
+        }
+
+        // characters are removed from the level when they die, while
+        // destroyed terrain only loses its hit points and doesn't die
+        for entity_id in action.hit_points_negative_iter(ecs) {
+            if !ecs.contains_destructible(entity_id) {
+                self.summary.events.push(GameEvent::Died(entity_id));
+            }
+        }
+
+        // except for the player, whose death ends the game instead
+        if action.contains_player_died() {
+            self.summary.events.push(GameEvent::Died(pc_id));
+        }
+
+        for entity_id in action.projectile_profile().insertion_iter() {
+            if !ecs.contains_projectile(entity_id) {
+                self.summary.events.push(GameEvent::ProjectileFired(entity_id));
+            }
+        }
+
+        if let Some(projectile_collision) = action.projectile_collision() {
+            self.summary.events.push(GameEvent::ProjectileHit(projectile_collision));
+        }
+
+        for (entity_id, door_state) in action.door_state_profile().insertion_copy_iter() {
+            if door_state.is_open() && ecs.door_state(entity_id).map_or(false, DoorState::is_closed) {
+                self.summary.events.push(GameEvent::DoorOpened(entity_id));
+            }
+        }
+
+        if let Some(level_switch_action) = action.level_switch_action() {
+            self.summary.events.push(GameEvent::LevelSwitch(level_switch_action));
+        }
+    }
+
+    /// Notifies each listener of the changes found by the last call to
+    /// `summarise`, if there were any
+    pub fn publish(&mut self, ecs: &EcsCtx) {
+        if self.summary.events.is_empty() {
+            return;
+        }
+
+        for listener in self.listeners.iter_mut() {
+            listener.notify(&self.summary, ecs);
+        }
+    }
+}
@@ -477,9 +477,19 @@ impl KnowledgeRenderer for AnsiKnowledgeRenderer {
 
         let hit_points = entity.hit_points().expect("Entity missing hit_points");
 
-        let health_text = format!(" ❤ {}/{}", hit_points.current(), hit_points.max());
+        let mut hud_text = format!(" ❤ {}/{}", hit_points.current(), hit_points.max());
 
-        for ch in health_text.chars() {
+        if let Some(magazine) = entity.magazine() {
+            hud_text.push_str(&format!("  Ammo {}/{} ({})", magazine.loaded(), magazine.capacity(), magazine.reserve()));
+        }
+
+        if let Some(status_effects) = entity.status_effects() {
+            for effect in status_effects.iter() {
+                hud_text.push_str(&format!("  {} {}", effect.effect_type.short_name(), effect.duration));
+            }
+        }
+
+        for ch in hud_text.chars() {
             if cursor >= self.renderer.hud_window.width() as isize {
                 break;
             }
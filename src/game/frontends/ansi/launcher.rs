@@ -44,9 +44,5 @@ pub fn launch(args: Arguments) -> ExternalResult<()> {
         },
     };
 
-    let mut game = GameCtx::new(renderer, input_source, args.rng_seed, GAME_WIDTH, GAME_HEIGHT);
-
-    game.run(args)?;
-
-    Ok(())
+    run_game(renderer, input_source, args)
 }
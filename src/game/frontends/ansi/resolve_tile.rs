@@ -15,10 +15,24 @@ pub fn resolve_tile(tile_type: TileType) -> ansi::ComplexTile {
             back: ansi::SimpleTile::Foreground('█', ansi::colours::GREY,
                                                ansi::styles::NONE),
         },
+        TileType::CrackedWall => ansi::ComplexTile::Wall {
+            front: ansi::SimpleTile::Full {
+                ch: '▄',
+                fg: ansi::colours::YELLOW,
+                bg: ansi::colours::DARK_GREY,
+                style: ansi::styles::NONE,
+            },
+            back: ansi::SimpleTile::Foreground('█', ansi::colours::DARK_GREY,
+                                               ansi::styles::NONE),
+        },
+        TileType::Rubble => ansi::foreground(':', ansi::colours::GREY,
+                                             ansi::styles::NONE),
         TileType::Tree => ansi::foreground('&', ansi::colours::GREEN,
                                            ansi::styles::NONE),
         TileType::DeadTree => ansi::foreground('£', ansi::colours::YELLOW,
                                                ansi::styles::BOLD),
+        TileType::TreeStump => ansi::foreground('_', ansi::colours::YELLOW,
+                                                ansi::styles::NONE),
         TileType::Floor => ansi::full('.', ansi::colours::WHITE,
                                       COMMON_BG,
                                       ansi::styles::NONE),
@@ -31,14 +45,26 @@ pub fn resolve_tile(tile_type: TileType) -> ansi::ComplexTile {
         TileType::ClosedDoor => ansi::full('+', ansi::colours::WHITE,
                                            ansi::colours::DARK_GREY,
                                            ansi::styles::NONE),
+        TileType::BrokenDoor => ansi::foreground('\'', ansi::colours::WHITE,
+                                                 ansi::styles::NONE),
         TileType::Bullet => ansi::foreground('*', ansi::colours::RED,
                                              ansi::styles::NONE),
+        TileType::Pellet => ansi::foreground('·', ansi::colours::BRIGHT_YELLOW,
+                                             ansi::styles::NONE),
+        TileType::PiercingRound => ansi::foreground('~', ansi::colours::BRIGHT_YELLOW,
+                                                    ansi::styles::BOLD),
+        TileType::ExplosiveRound => ansi::foreground('o', ansi::colours::BRIGHT_RED,
+                                                     ansi::styles::BOLD),
+        TileType::SilverBullet => ansi::foreground('*', ansi::colours::WHITE,
+                                                   ansi::styles::BOLD),
         TileType::Player => ansi::foreground('@', ansi::colours::WHITE,
                                              ansi::styles::BOLD),
         TileType::TerrorPillar => ansi::foreground('t', ansi::colours::GREEN,
                                                    ansi::styles::BOLD),
         TileType::TerrorFly => ansi::foreground('T', ansi::colours::GREEN,
                                                 ansi::styles::BOLD),
+        TileType::Hunter => ansi::foreground('h', ansi::colours::YELLOW,
+                                             ansi::styles::BOLD),
         TileType::Book => ansi::foreground('=', ansi::colours::BRIGHT_MAGENTA,
                                                 ansi::styles::BOLD),
         TileType::UpStairs => ansi::foreground('<', ansi::colours::WHITE,
@@ -0,0 +1,239 @@
+use std::cell::{Ref, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use game::*;
+use game::data::*;
+use coord::Coord;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeadlessCell {
+    pub foreground: Option<TileType>,
+    pub background: Option<TileType>,
+    pub visible: bool,
+    pub tear: bool,
+}
+
+impl<'a> From<&'a CellDrawInfo> for HeadlessCell {
+    fn from(cell: &'a CellDrawInfo) -> Self {
+        HeadlessCell {
+            foreground: cell.foreground,
+            background: cell.background,
+            visible: cell.visible,
+            tear: cell.tear,
+        }
+    }
+}
+
+impl Default for HeadlessCell {
+    fn default() -> Self {
+        HeadlessCell {
+            foreground: None,
+            background: None,
+            visible: false,
+            tear: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HeadlessOverlay {
+    AimLine(Vec<Coord>),
+    ExamineCursor(Coord),
+    Death,
+}
+
+impl<'a> From<&'a RenderOverlay> for HeadlessOverlay {
+    fn from(overlay: &'a RenderOverlay) -> Self {
+        match *overlay {
+            RenderOverlay::AimLine(ref aim_line) => HeadlessOverlay::AimLine(aim_line.iter().collect()),
+            RenderOverlay::ExamineCursor(coord) => HeadlessOverlay::ExamineCursor(coord),
+            RenderOverlay::Death => HeadlessOverlay::Death,
+        }
+    }
+}
+
+/// Snapshot of the game window, indexed by screen-space coordinates
+#[derive(Clone, Debug)]
+pub struct HeadlessGameWindow {
+    pub width: usize,
+    pub height: usize,
+    pub world_offset: Coord,
+    pub cells: Vec<HeadlessCell>,
+    pub overlay: Option<HeadlessOverlay>,
+}
+
+impl HeadlessGameWindow {
+    pub fn new(width: usize, height: usize) -> Self {
+        HeadlessGameWindow {
+            width: width,
+            height: height,
+            world_offset: Coord::new(0, 0),
+            cells: vec![HeadlessCell::default(); width * height],
+            overlay: None,
+        }
+    }
+
+    fn index(&self, coord: Coord) -> Option<usize> {
+        if coord.x < 0 || coord.y < 0 || coord.x >= self.width as isize || coord.y >= self.height as isize {
+            None
+        } else {
+            Some(coord.y as usize * self.width + coord.x as usize)
+        }
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&HeadlessCell> {
+        self.index(coord).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut HeadlessCell> {
+        self.index(coord).map(move |i| &mut self.cells[i])
+    }
+
+    /// Looks up a cell by its coordinate in world-space
+    pub fn get_world(&self, coord: Coord) -> Option<&HeadlessCell> {
+        self.get(coord - self.world_offset)
+    }
+
+    /// Returns the world-space coordinates of all visible cells whose foreground is the given tile
+    pub fn find_visible(&self, tile: TileType) -> Vec<Coord> {
+        let mut coords = Vec::new();
+        for (i, cell) in self.cells.iter().enumerate() {
+            if cell.visible && cell.foreground == Some(tile) {
+                let screen_coord = Coord::new((i % self.width) as isize, (i / self.width) as isize);
+                coords.push(screen_coord + self.world_offset);
+            }
+        }
+        coords
+    }
+
+    pub fn contains_visible(&self, tile: TileType) -> bool {
+        self.cells.iter().any(|cell| cell.visible && cell.foreground == Some(tile))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct HeadlessHud {
+    pub hit_points: HitPoints,
+    pub magazine: Option<Magazine>,
+    pub status_effects: Option<StatusEffects>,
+}
+
+#[derive(Clone, Debug)]
+pub enum HeadlessFullscreen {
+    Log(Vec<String>),
+    Text(Vec<String>),
+    Menu {
+        prelude: Vec<String>,
+        items: Vec<String>,
+        selected: usize,
+    },
+}
+
+/// Everything that was on the display when it was published
+#[derive(Clone, Debug)]
+pub struct HeadlessFrame {
+    pub game_window: Option<HeadlessGameWindow>,
+    pub log: Vec<String>,
+    pub hud: Option<HeadlessHud>,
+    pub fullscreen: Option<HeadlessFullscreen>,
+}
+
+impl HeadlessFrame {
+    pub fn new() -> Self {
+        HeadlessFrame {
+            game_window: None,
+            log: Vec::new(),
+            hud: None,
+            fullscreen: None,
+        }
+    }
+
+    pub fn log_contains(&self, text: &str) -> bool {
+        self.log.iter().any(|line| line.contains(text))
+    }
+}
+
+struct HeadlessRecordingInner {
+    frames: VecDeque<HeadlessFrame>,
+    max_frames: Option<usize>,
+    num_published: usize,
+}
+
+/// Shared handle to the frames published by a `HeadlessKnowledgeRenderer`.
+/// Cloning the handle does not copy the frames, so a handle can be kept
+/// after the renderer has been moved into a `GameCtx`.
+#[derive(Clone)]
+pub struct HeadlessRecording(Rc<RefCell<HeadlessRecordingInner>>);
+
+impl HeadlessRecording {
+    pub fn new() -> Self {
+        Self::new_internal(None)
+    }
+
+    /// Only the most recent `max_frames` frames are retained
+    pub fn with_max_frames(max_frames: usize) -> Self {
+        Self::new_internal(Some(max_frames))
+    }
+
+    fn new_internal(max_frames: Option<usize>) -> Self {
+        HeadlessRecording(Rc::new(RefCell::new(HeadlessRecordingInner {
+            frames: VecDeque::new(),
+            max_frames: max_frames,
+            num_published: 0,
+        })))
+    }
+
+    pub fn push(&self, frame: HeadlessFrame) {
+        let mut inner = self.0.borrow_mut();
+        inner.num_published += 1;
+
+        match inner.max_frames {
+            Some(0) => return,
+            Some(max_frames) => {
+                while inner.frames.len() >= max_frames {
+                    inner.frames.pop_front();
+                }
+            }
+            None => {}
+        }
+
+        inner.frames.push_back(frame);
+    }
+
+    pub fn frames(&self) -> Ref<VecDeque<HeadlessFrame>> {
+        Ref::map(self.0.borrow(), |inner| &inner.frames)
+    }
+
+    pub fn last(&self) -> Option<HeadlessFrame> {
+        self.0.borrow().frames.back().cloned()
+    }
+
+    /// Total number of frames published, including any that were discarded
+    pub fn num_published(&self) -> usize {
+        self.0.borrow().num_published
+    }
+
+    pub fn clear(&self) {
+        self.0.borrow_mut().frames.clear();
+    }
+}
+
+pub fn message_to_string(message: &Message) -> String {
+    let mut string = String::new();
+    for part in message {
+        match *part {
+            MessagePart::Text(ref text) => string.push_str(text.string_ref()),
+            MessagePart::Newline => string.push('\n'),
+        }
+    }
+    string
+}
+
+pub fn text_message_to_string(message: &TextMessage) -> String {
+    let mut string = String::new();
+    for part in message {
+        string.push_str(part.string_ref());
+    }
+    string
+}
@@ -0,0 +1,42 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+use game::*;
+
+/// Input source which yields a predetermined sequence of input events.
+/// Clones share the same queue, so events consumed through the copy held by
+/// the player's behaviour are not replayed by the copy held by the game.
+#[derive(Clone)]
+pub struct ScriptedInputSource {
+    events: Rc<RefCell<VecDeque<InputEvent>>>,
+}
+
+impl ScriptedInputSource {
+    pub fn new<I: IntoIterator<Item=InputEvent>>(events: I) -> Self {
+        ScriptedInputSource {
+            events: Rc::new(RefCell::new(events.into_iter().collect())),
+        }
+    }
+
+    pub fn push(&self, event: InputEvent) {
+        self.events.borrow_mut().push_back(event);
+    }
+
+    pub fn extend<I: IntoIterator<Item=InputEvent>>(&self, events: I) {
+        self.events.borrow_mut().extend(events);
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.events.borrow().len()
+    }
+}
+
+impl InputSource for ScriptedInputSource {
+    /// Panics if the script has been exhausted. Most of the game waits for
+    /// input in a loop, so running out of input would otherwise hang.
+    fn next_input(&mut self) -> Option<InputEvent> {
+        let event = self.events.borrow_mut().pop_front().expect("Scripted input exhausted");
+        Some(event)
+    }
+}
@@ -0,0 +1,187 @@
+use std::cmp;
+
+use ecs::*;
+use game::*;
+use game::frontends::headless::*;
+use coord::Coord;
+
+const MESSAGE_LOG_NUM_LINES: usize = 4;
+const HUD_NUM_LINES: usize = 1;
+const FULLSCREEN_NUM_COLS: usize = 80;
+
+/// Renderer which draws to in-memory buffers rather than a physical display.
+/// Each call to `publish` appends a snapshot of the display to a `HeadlessRecording`.
+pub struct HeadlessKnowledgeRenderer {
+    width: usize,
+    height: usize,
+    scroll_position: Coord,
+    buffers: RendererBuffers,
+    display: HeadlessFrame,
+    recording: HeadlessRecording,
+}
+
+impl HeadlessKnowledgeRenderer {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::new_with_recording(width, height, HeadlessRecording::new())
+    }
+
+    pub fn new_with_recording(width: usize, height: usize, recording: HeadlessRecording) -> Self {
+        HeadlessKnowledgeRenderer {
+            width: width,
+            height: height,
+            scroll_position: Coord::new(0, 0),
+            buffers: RendererBuffers::new(width, height, MESSAGE_LOG_NUM_LINES),
+            display: HeadlessFrame::new(),
+            recording: recording,
+        }
+    }
+
+    pub fn recording(&self) -> HeadlessRecording {
+        self.recording.clone()
+    }
+
+    fn total_height(&self) -> usize {
+        self.height + HUD_NUM_LINES + MESSAGE_LOG_NUM_LINES
+    }
+
+    fn draw_internal(&mut self) -> HeadlessGameWindow {
+        let mut window = HeadlessGameWindow::new(self.width, self.height);
+        window.world_offset = self.scroll_position;
+
+        for (coord, cell) in izip!(self.buffers.tiles.coord_iter(), self.buffers.tiles.iter()) {
+            if let Some(headless_cell) = window.get_mut(coord) {
+                *headless_cell = HeadlessCell::from(cell);
+            }
+        }
+
+        window
+    }
+}
+
+impl KnowledgeRenderer for HeadlessKnowledgeRenderer {
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn world_offset(&self) -> Coord {
+        self.scroll_position
+    }
+
+    fn update_game_window_buffer(&mut self, knowledge: &DrawableKnowledgeLevel, turn_id: u64, position: Coord) {
+        self.scroll_position = self.centre_offset(position);
+        self.buffers.tiles.update(knowledge, turn_id, self.scroll_position);
+    }
+
+    fn draw_game_window(&mut self) {
+        self.display.game_window = Some(self.draw_internal());
+        self.display.fullscreen = None;
+    }
+
+    fn draw_game_window_with_overlay(&mut self, overlay: &RenderOverlay) {
+        let mut window = self.draw_internal();
+        window.overlay = Some(HeadlessOverlay::from(overlay));
+        self.display.game_window = Some(window);
+        self.display.fullscreen = None;
+    }
+
+    fn draw_log(&mut self) {
+        self.display.log = self.buffers.message_log.iter().map(message_to_string).collect();
+    }
+
+    fn update_log_buffer(&mut self, messages: &MessageLog, language: &Box<Language>) {
+        for message in self.buffers.message_log.iter_mut() {
+            message.clear();
+        }
+        for (log_entry, message) in izip!(messages.tail(MESSAGE_LOG_NUM_LINES), &mut self.buffers.message_log) {
+            language.translate_repeated(log_entry.message, log_entry.repeated, message);
+        }
+    }
+
+    fn fullscreen_log(&mut self, message_log: &MessageLog, offset: usize, language: &Box<Language>) {
+        let mut lines = Vec::new();
+        let mut message = Message::new();
+        for log_entry in message_log.tail_with_offset(self.fullscreen_log_num_rows(), offset) {
+            message.clear();
+            language.translate_repeated(log_entry.message, log_entry.repeated, &mut message);
+            lines.push(message_to_string(&message));
+        }
+
+        self.display.fullscreen = Some(HeadlessFullscreen::Log(lines));
+    }
+
+    fn fullscreen_log_num_rows(&self) -> usize {
+        self.total_height()
+    }
+
+    fn fullscreen_log_num_cols(&self) -> usize {
+        FULLSCREEN_NUM_COLS
+    }
+
+    fn fullscreen_wrapped_translated_message(&mut self, wrapped: &Vec<TextMessage>, offset: usize) {
+        let end_idx = cmp::min(wrapped.len(), offset + self.total_height());
+        let start_idx = cmp::min(offset, end_idx);
+        let lines = wrapped[start_idx..end_idx].iter().map(text_message_to_string).collect();
+
+        self.display.fullscreen = Some(HeadlessFullscreen::Text(lines));
+    }
+
+    fn draw_hud(&mut self, entity: EntityRef, _language: &Box<Language>) {
+        self.display.hud = entity.hit_points().map(|hit_points| {
+            HeadlessHud {
+                hit_points: hit_points,
+                magazine: entity.magazine(),
+                status_effects: entity.status_effects(),
+            }
+        });
+    }
+
+    fn fullscreen_menu<T>(&mut self, prelude: Option<MessageType>, menu: &SelectMenu<T>, state: &SelectMenuState, language: &Box<Language>) {
+        let mut message = Message::new();
+
+        let prelude_lines = if let Some(message_type) = prelude {
+            let mut wrapped = Vec::new();
+            language.translate(message_type, &mut message);
+            self.fullscreen_wrap(&message, &mut wrapped);
+            wrapped.iter().map(text_message_to_string).collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut items = Vec::new();
+        let mut selected = 0;
+
+        for (index, (item_state, item)) in state.iter(menu).enumerate() {
+            message.clear();
+            language.translate(MessageType::Menu(item.message()), &mut message);
+            items.push(message_to_string(&message));
+
+            if item_state == SelectMenuItemState::Selected {
+                selected = index;
+            }
+        }
+
+        self.display.fullscreen = Some(HeadlessFullscreen::Menu {
+            prelude: prelude_lines,
+            items: items,
+            selected: selected,
+        });
+    }
+
+    fn log_num_lines(&self) -> usize {
+        MESSAGE_LOG_NUM_LINES
+    }
+
+    fn publish(&mut self) {
+        self.recording.push(self.display.clone());
+    }
+
+    fn reset_buffers(&mut self) {
+        self.buffers.reset();
+        self.display = HeadlessFrame::new();
+    }
+}
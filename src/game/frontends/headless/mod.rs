@@ -0,0 +1,10 @@
+mod knowledge_renderer;
+mod input;
+mod frame;
+
+pub use self::knowledge_renderer::*;
+pub use self::input::*;
+pub use self::frame::*;
+
+#[cfg(test)]
+mod tests;
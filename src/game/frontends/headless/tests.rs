@@ -0,0 +1,946 @@
+use std::env;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::process;
+use std::path::{Path, PathBuf};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use game::*;
+use game::data::{HitPoints, Magazine, StatusEffect, StatusEffects, StatusEffectType};
+use game::frontends::headless::*;
+use coord::Coord;
+use ecs::{EcsCtx, EcsAction};
+
+const SEED: usize = 0;
+
+const START_COORD: Coord = Coord { x: 18, y: 14 };
+const DEMO_B_START_COORD: Coord = Coord { x: 30, y: 4 };
+
+/// The door on the east side of the building west of the starting position
+const DOOR_COORD: Coord = Coord { x: 13, y: 11 };
+
+fn user_dir(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("howl-headless-{}-{}", name, process::id()));
+    fs::create_dir_all(&path).expect("Failed to create user directory");
+    path
+}
+
+fn new_game() -> Vec<InputEvent> {
+    // select "New Game" from the main menu, pick the first save slot, then dismiss the intro
+    vec![InputEvent::Return, InputEvent::Return, InputEvent::Space]
+}
+
+fn run_scenario(name: &str, events: Vec<InputEvent>) -> HeadlessRecording {
+    run_scenario_with_args(name, events, Arguments::default())
+}
+
+fn run_scenario_with_args(name: &str, events: Vec<InputEvent>, args: Arguments) -> HeadlessRecording {
+    let user_path = user_dir(name);
+    let recording = run_in_user_dir(&user_path, events, args);
+    fs::remove_dir_all(user_path).ok();
+
+    recording
+}
+
+fn run_scenario_with_listeners(name: &str, events: Vec<InputEvent>, listeners: Vec<Box<CommitListener>>) -> HeadlessRecording {
+    let user_path = user_dir(name);
+    let (recording, _) = run_in_user_dir_with_listeners(&user_path, events, Arguments::default(), listeners);
+    fs::remove_dir_all(user_path).ok();
+
+    recording
+}
+
+fn run_in_user_dir(user_path: &Path, events: Vec<InputEvent>, args: Arguments) -> HeadlessRecording {
+    let (recording, _) = run_in_user_dir_with_listeners(user_path, events, args, Vec::new());
+    recording
+}
+
+/// Runs a game which writes a state trace to, or checks its state against a
+/// trace from, the paths in the arguments, returning where it diverged
+fn check_state_in_user_dir(user_path: &Path, events: Vec<InputEvent>, args: Arguments) -> Option<StateDivergence> {
+    let (_, divergence) = run_in_user_dir_with_listeners(user_path, events, args, Vec::new());
+    divergence
+}
+
+fn run_in_user_dir_with_listeners(user_path: &Path,
+                                  events: Vec<InputEvent>,
+                                  mut args: Arguments,
+                                  listeners: Vec<Box<CommitListener>>) -> (HeadlessRecording, Option<StateDivergence>) {
+    let renderer = HeadlessKnowledgeRenderer::new(GAME_WIDTH, GAME_HEIGHT);
+    let recording = renderer.recording();
+    let input = ScriptedInputSource::new(events);
+
+    args.rng_seed = SEED;
+    args.user_path = user_path.to_path_buf();
+    args.shared_user_path = user_path.to_path_buf();
+
+    let writer = args.state_trace_path.as_ref().map(|path| {
+        state_trace_file::StateTraceWriter::create(path).expect("Failed to create state trace")
+    });
+
+    let mut game = GameCtx::new(renderer, input.clone(), SEED, GAME_WIDTH, GAME_HEIGHT);
+    game.set_clock(Box::new(InstantClock));
+    game.set_state_trace(StateTrace::new(writer, args.state_reference.take()));
+    for listener in listeners {
+        game.subscribe(listener);
+    }
+    game.run(args).expect("Game returned an error");
+
+    assert_eq!(input.remaining(), 0, "Game finished without consuming all input");
+
+    (recording, game.state_divergence())
+}
+
+fn last_game_window(recording: &HeadlessRecording) -> HeadlessGameWindow {
+    recording.frames().iter().rev()
+        .filter_map(|frame| frame.game_window.clone())
+        .next()
+        .expect("No game window was published")
+}
+
+/// The lines of the first fullscreen text containing `needle`
+fn find_fullscreen_text(recording: &HeadlessRecording, needle: &str) -> Option<Vec<String>> {
+    recording.frames().iter().filter_map(|frame| {
+        match frame.fullscreen {
+            Some(HeadlessFullscreen::Text(ref lines)) if lines.iter().any(|line| line.contains(needle)) => Some(lines.clone()),
+            _ => None,
+        }
+    }).next()
+}
+
+#[test]
+fn new_game_and_quit() {
+    let mut events = new_game();
+    events.push(InputEvent::Quit);
+
+    let recording = run_scenario("new_game_and_quit", events);
+    let frame = recording.last().expect("No frames were published");
+
+    assert!(frame.log_contains("Welcome to HOWL!"));
+
+    let hit_points = frame.hud.expect("No hud was published").hit_points;
+    assert_eq!(hit_points.current(), 10);
+
+    let window = frame.game_window.expect("No game window was published");
+    assert_eq!(window.find_visible(TileType::Player), vec![START_COORD]);
+}
+
+#[test]
+fn walk_onto_book() {
+    let mut events = new_game();
+
+    // the book is 3 cells east of the starting position
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Quit);
+
+    let recording = run_scenario("walk_onto_book", events);
+
+    let window = last_game_window(&recording);
+    assert_eq!(window.find_visible(TileType::Player), vec![DEMO_B_START_COORD]);
+    assert!(!window.contains_visible(TileType::Tree));
+}
+
+#[test]
+fn walk_into_tree() {
+    let mut events = new_game();
+
+    // there is a tree 3 cells east of the cell south of the starting position
+    events.push(InputEvent::Down);
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Quit);
+
+    let recording = run_scenario("walk_into_tree", events);
+    let frames = recording.frames();
+
+    // trees can be destroyed, so walking into one attacks it
+    assert!(frames.iter().any(|frame| frame.log_contains("the tree")));
+
+    let window = last_game_window(&recording);
+    assert_eq!(window.find_visible(TileType::Player), vec![START_COORD + Coord::new(2, 1)]);
+}
+
+/// Keeps the enemies on a level out of the way
+fn stun_enemies(level: &Level, action: &mut EcsAction) {
+    let mut status_effects = StatusEffects::new();
+    status_effects.apply(StatusEffect::new(StatusEffectType::Stunned, 1000));
+    for y in 0..level.spatial_hash.height() {
+        for x in 0..level.spatial_hash.width() {
+            let coord = Coord::new(x as isize, y as isize);
+            for entity_id in level.spatial_hash.get(coord).entity_id_iter() {
+                if level.ecs.contains_enemy(entity_id) {
+                    action.insert_status_effects(entity_id, status_effects);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn walk_through_destroyed_door() {
+    let user_path = user_dir("walk_through_destroyed_door");
+
+    let mut events = new_game();
+    events.push(InputEvent::Quit);
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    let (mut game_state, _) = save_file::load(save_slot::slot_path(&user_path, 1)).expect("Failed to load save");
+    let mut action = EcsAction::new();
+    {
+        let (pc_id, level) = game_state.pc_level_mut().expect("Player is missing");
+        assert!(level.spatial_hash.get(DOOR_COORD).any_door().is_some());
+
+        // a player who can't open doors attacks them instead
+        action.remove_door_opener(pc_id);
+        action.insert_position(pc_id, DOOR_COORD + Coord::new(1, 0));
+
+        stun_enemies(level, &mut action);
+    }
+    game_state.commit_to_pc_level(&mut action);
+    save_slot::save(&user_path, 1, game_state).1.expect("Failed to save");
+
+    // select "Continue" from the main menu, then keep walking west through
+    // the door once it has been destroyed
+    let mut events = vec![InputEvent::Return];
+    for _ in 0..12 {
+        events.push(InputEvent::Left);
+    }
+    events.push(InputEvent::Quit);
+
+    let recording = run_in_user_dir(&user_path, events, Arguments::default());
+
+    fs::remove_dir_all(user_path).ok();
+
+    assert!(recording.frames().iter().any(|frame| frame.log_contains("The door is destroyed.")));
+
+    let window = last_game_window(&recording);
+    let player = window.find_visible(TileType::Player);
+    assert_eq!(player.len(), 1);
+    assert!(player[0].x < DOOR_COORD.x && player[0].y == DOOR_COORD.y);
+
+    // the broken door no longer blocks the view out of the building
+    assert_eq!(window.find_visible(TileType::BrokenDoor), vec![DOOR_COORD]);
+    assert!(window.get_world(DOOR_COORD + Coord::new(1, 0)).map_or(false, |cell| cell.visible));
+}
+
+#[test]
+fn rewind_walk() {
+    let mut events = new_game();
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Char('u'));
+    events.push(InputEvent::Char('u'));
+    events.push(InputEvent::Quit);
+
+    // rewinding is only available in debug mode
+    let mut args = Arguments::default();
+    args.debug = true;
+
+    let recording = run_scenario_with_args("rewind_walk", events, args);
+
+    let window = last_game_window(&recording);
+    assert_eq!(window.find_visible(TileType::Player), vec![START_COORD]);
+}
+
+#[test]
+fn rule_trace_viewer() {
+    let mut events = new_game();
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Char('R'));
+    events.push(InputEvent::Char('R'));
+    events.push(InputEvent::Quit);
+
+    // rules are only traced in debug mode
+    let mut args = Arguments::default();
+    args.debug = true;
+
+    let recording = run_scenario_with_args("rule_trace_viewer", events, args);
+
+    let trace = find_fullscreen_text(&recording, "RULE TRACE").expect("Rule trace was not shown");
+
+    assert!(trace.iter().any(|line| line.contains("action ")));
+    assert!(trace.iter().any(|line| line.contains("OpenDoor: Accept")));
+}
+
+struct EventRecorder(Rc<RefCell<Vec<GameEvent>>>);
+
+impl CommitListener for EventRecorder {
+    fn notify(&mut self, summary: &CommitSummary, _ecs: &EcsCtx) {
+        self.0.borrow_mut().extend(summary.events.iter().cloned());
+    }
+}
+
+#[test]
+fn committed_actions_are_published() {
+    let mut events = new_game();
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Quit);
+
+    let game_events = Rc::new(RefCell::new(Vec::new()));
+    let recorder: Box<CommitListener> = Box::new(EventRecorder(game_events.clone()));
+    run_scenario_with_listeners("committed_actions_are_published", events, vec![recorder]);
+
+    let destination = Coord::new(START_COORD.x + 1, START_COORD.y);
+    assert!(game_events.borrow().iter().any(|event| {
+        match *event {
+            GameEvent::Moved { from, to, .. } => from == Some(START_COORD) && to == destination,
+            _ => false,
+        }
+    }));
+}
+
+#[test]
+fn fire_at_terror_pillar() {
+    let mut events = new_game();
+
+    // aim at the nearest target, nudge the aim line, and fire
+    events.push(InputEvent::Char('f'));
+    events.push(InputEvent::Left);
+    events.push(InputEvent::Return);
+    events.push(InputEvent::Quit);
+
+    let recording = run_scenario("fire_at_terror_pillar", events);
+    let frames = recording.frames();
+
+    assert!(frames.iter().any(|frame| {
+        frame.game_window.as_ref().map_or(false, |window| {
+            match window.overlay {
+                Some(HeadlessOverlay::AimLine(_)) => true,
+                _ => false,
+            }
+        })
+    }));
+
+    assert!(frames.iter().any(|frame| {
+        frame.game_window.as_ref().map_or(false, |window| window.contains_visible(TileType::Bullet))
+    }));
+}
+
+#[test]
+fn fire_spread_shot() {
+    let mut events = new_game();
+
+    // switch from bullets to a spread shot while aiming
+    events.push(InputEvent::Char('f'));
+    events.push(InputEvent::Char('a'));
+    events.push(InputEvent::Left);
+    events.push(InputEvent::Return);
+    events.push(InputEvent::Quit);
+
+    let recording = run_scenario("fire_spread_shot", events);
+    let frames = recording.frames();
+
+    assert!(frames.iter().any(|frame| frame.log_contains("Firing: spread shot")));
+
+    assert!(frames.iter().any(|frame| {
+        frame.game_window.as_ref().map_or(false, |window| window.contains_visible(TileType::Pellet))
+    }));
+    assert!(!frames.iter().any(|frame| {
+        frame.game_window.as_ref().map_or(false, |window| window.contains_visible(TileType::Bullet))
+    }));
+}
+
+#[test]
+fn fire_silver_bullet() {
+    let mut events = new_game();
+
+    // cycle through every other projectile type to reach silver bullets
+    events.push(InputEvent::Char('f'));
+    for _ in 0..4 {
+        events.push(InputEvent::Char('a'));
+    }
+    events.push(InputEvent::Left);
+    events.push(InputEvent::Return);
+    events.push(InputEvent::Quit);
+
+    let recording = run_scenario("fire_silver_bullet", events);
+    let frames = recording.frames();
+
+    assert!(frames.iter().any(|frame| frame.log_contains("Firing: silver bullet")));
+
+    assert!(frames.iter().any(|frame| {
+        frame.game_window.as_ref().map_or(false, |window| window.contains_visible(TileType::SilverBullet))
+    }));
+}
+
+#[test]
+fn character_stats_after_firing() {
+    let mut events = new_game();
+
+    // fire as above, then view and dismiss the statistics screen
+    events.push(InputEvent::Char('f'));
+    events.push(InputEvent::Left);
+    events.push(InputEvent::Return);
+    events.push(InputEvent::Char('s'));
+    events.push(InputEvent::Space);
+    events.push(InputEvent::Quit);
+
+    let recording = run_scenario("character_stats_after_firing", events);
+
+    let stats = find_fullscreen_text(&recording, "STATISTICS").expect("Statistics were not shown");
+
+    assert!(stats.iter().any(|line| line.contains("Bullets fired: 1")));
+    assert!(stats.iter().any(|line| line.contains("Levels entered: 0")));
+    assert!(!stats.iter().any(|line| line.contains("Tiles explored: 0")));
+}
+
+#[test]
+fn reload_after_firing() {
+    let mut events = new_game();
+
+    // fire as above, then reload
+    events.push(InputEvent::Char('f'));
+    events.push(InputEvent::Left);
+    events.push(InputEvent::Return);
+    events.push(InputEvent::Char('r'));
+    events.push(InputEvent::Quit);
+
+    let recording = run_scenario("reload_after_firing", events);
+
+    let magazines: Vec<Magazine> = recording.frames().iter().filter_map(|frame| {
+        frame.hud.as_ref().and_then(|hud| hud.magazine)
+    }).collect();
+
+    let first = magazines.first().expect("No ammunition was shown");
+    assert_eq!((first.loaded(), first.reserve()), (6, 12));
+    assert!(magazines.iter().any(|magazine| magazine.loaded() == 5));
+
+    let last = magazines.last().unwrap();
+    assert_eq!((last.loaded(), last.reserve()), (6, 11));
+
+    let frame = recording.last().expect("No frames were published");
+    assert!(frame.log_contains("I reload."));
+}
+
+#[test]
+fn reload_with_nothing_to_reload() {
+    let mut events = new_game();
+
+    // the magazine starts full
+    events.push(InputEvent::Char('r'));
+    events.push(InputEvent::Quit);
+
+    let user_path = user_dir("reload_with_nothing_to_reload");
+    let recording = run_in_user_dir(&user_path, events, Arguments::default());
+    let loaded = save_file::load(save_slot::slot_path(&user_path, 1));
+    fs::remove_dir_all(user_path).ok();
+
+    let frame = recording.last().expect("No frames were published");
+    assert!(frame.log_contains("My gun is already loaded."));
+
+    // the attempt doesn't use a turn
+    let (game_state, _) = loaded.expect("Failed to load save");
+    assert_eq!(game_state.stats().pc_turns, 0);
+}
+
+#[test]
+fn stun_skips_a_turn() {
+    let user_path = user_dir("stun_skips_a_turn");
+
+    let mut events = new_game();
+    events.push(InputEvent::Quit);
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    let (mut game_state, _) = save_file::load(save_slot::slot_path(&user_path, 1)).expect("Failed to load save");
+    {
+        let (pc_id, level) = game_state.pc_level_mut().expect("Player is missing");
+        let mut status_effects = StatusEffects::new();
+        status_effects.apply(StatusEffect::new(StatusEffectType::Stunned, 1));
+        level.ecs.insert_status_effects(pc_id, status_effects);
+    }
+    save_slot::save(&user_path, 1, game_state).1.expect("Failed to save");
+
+    // select "Continue" from the main menu, then quit as soon as the player can act
+    let recording = run_in_user_dir(&user_path, vec![InputEvent::Return, InputEvent::Quit], Arguments::default());
+    let loaded = save_file::load(save_slot::slot_path(&user_path, 1));
+
+    fs::remove_dir_all(user_path).ok();
+
+    assert!(recording.frames().iter().any(|frame| frame.log_contains("I am no longer stunned.")));
+
+    let (game_state, _) = loaded.expect("Failed to load save");
+    assert_eq!(game_state.stats().pc_turns, 1);
+}
+
+fn wait_until_killed_events() -> Vec<InputEvent> {
+    let mut events = new_game();
+
+    // the nearby terror pillars will eventually kill a player who doesn't move
+    for _ in 0..1000 {
+        events.push(InputEvent::Char('.'));
+    }
+
+    events
+}
+
+/// Waits until the player is killed, then quits from the main menu
+fn run_until_killed(user_path: &Path) -> HeadlessRecording {
+    // surplus waits are ignored by the main menu, so select "Quit", the last item
+    let mut events = wait_until_killed_events();
+    events.push(InputEvent::Up);
+    events.push(InputEvent::Return);
+
+    run_in_user_dir(user_path, events, Arguments::default())
+}
+
+fn find_death_frame(recording: &HeadlessRecording) -> HeadlessFrame {
+    recording.frames().iter().find(|frame| {
+        frame.game_window.as_ref().map_or(false, |window| window.overlay == Some(HeadlessOverlay::Death))
+    }).cloned().expect("Player did not die")
+}
+
+#[test]
+fn wait_until_killed() {
+    let user_path = user_dir("wait_until_killed");
+    let recording = run_until_killed(&user_path);
+    fs::remove_dir_all(user_path).ok();
+
+    let death_frame = find_death_frame(&recording);
+    assert!(death_frame.log_contains("YOU DIED"));
+    assert!(!death_frame.hud.as_ref().expect("No hud was published").hit_points.is_positive());
+
+    match recording.frames().back().and_then(|frame| frame.fullscreen.as_ref()) {
+        Some(&HeadlessFullscreen::Menu { ref items, .. }) => {
+            assert!(!items.iter().any(|item| item == "Continue"));
+        }
+        other => panic!("Expected main menu, found {:?}", other),
+    }
+}
+
+#[test]
+fn terror_pillar_hits_player() {
+    let user_path = user_dir("terror_pillar_hits_player");
+    let recording = run_until_killed(&user_path);
+    fs::remove_dir_all(user_path).ok();
+
+    assert!(find_death_frame(&recording).log_contains("hits me"));
+}
+
+#[test]
+fn terror_pillar_terrifies_player() {
+    let user_path = user_dir("terror_pillar_terrifies_player");
+    let recording = run_until_killed(&user_path);
+    fs::remove_dir_all(user_path).ok();
+
+    let frames = recording.frames();
+    assert!(frames.iter().any(|frame| frame.log_contains("I am terrified.")));
+    assert!(frames.iter().any(|frame| {
+        frame.hud.as_ref().and_then(|hud| hud.status_effects).map_or(false, |status_effects| {
+            status_effects.is_active(StatusEffectType::Terrified)
+        })
+    }));
+}
+
+#[test]
+fn morgue_written_on_death() {
+    let user_path = user_dir("morgue_written_on_death");
+    run_until_killed(&user_path);
+
+    let morgue = fs::read_dir(&user_path).expect("Failed to read user directory")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .find(|path| path.file_name().and_then(|n| n.to_str()).map_or(false, |n| n.starts_with("morgue-")))
+        .map(|path| {
+            let mut contents = String::new();
+            File::open(path).and_then(|mut f| f.read_to_string(&mut contents)).expect("Failed to read morgue file");
+            contents
+        });
+
+    fs::remove_dir_all(user_path).ok();
+
+    let morgue = morgue.expect("No morgue file was written");
+    assert!(morgue.contains(&format!("Seed: {}", SEED)));
+    assert!(morgue.contains("YOU DIED"));
+    assert!(morgue.contains("Cause of death: Killer(TerrorPillar)"));
+    assert!(morgue.contains("@"));
+}
+
+#[test]
+fn bleeding_to_death_is_the_cause_of_death() {
+    let user_path = user_dir("bleeding_to_death_is_the_cause_of_death");
+
+    let mut events = new_game();
+    events.push(InputEvent::Quit);
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    let (mut game_state, _) = save_file::load(save_slot::slot_path(&user_path, 1)).expect("Failed to load save");
+    let mut action = EcsAction::new();
+    {
+        let (pc_id, level) = game_state.pc_level_mut().expect("Player is missing");
+        let max = level.ecs.hit_points(pc_id).expect("Player missing hit_points").max();
+        action.insert_hit_points(pc_id, HitPoints::new_with(max, 1));
+
+        let mut status_effects = StatusEffects::new();
+        status_effects.apply(StatusEffect::new(StatusEffectType::Bleeding, 10));
+        action.insert_status_effects(pc_id, status_effects);
+
+        stun_enemies(level, &mut action);
+    }
+    game_state.commit_to_pc_level(&mut action);
+    save_slot::save(&user_path, 1, game_state).1.expect("Failed to save");
+
+    // select "Continue" from the main menu and wait to bleed to death.
+    // Surplus waits are ignored by the main menu, so then select "Quit".
+    let mut events = vec![InputEvent::Return];
+    for _ in 0..5 {
+        events.push(InputEvent::Char('.'));
+    }
+    events.push(InputEvent::Up);
+    events.push(InputEvent::Return);
+
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    let history = run_history_file::load(&user_path);
+    fs::remove_dir_all(user_path).ok();
+
+    let history = history.expect("Failed to load run history");
+    let runs = history.high_scores();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].cause_of_death, Some(DeathCause::Bleeding));
+}
+
+#[test]
+fn high_score_recorded_on_death() {
+    let mut events = wait_until_killed_events();
+
+    // surplus waits are ignored by the main menu, so view the high scores, then select "Quit"
+    events.push(InputEvent::Down);
+    events.push(InputEvent::Down);
+    events.push(InputEvent::Down);
+    events.push(InputEvent::Return);
+    events.push(InputEvent::Return);
+    events.push(InputEvent::Down);
+    events.push(InputEvent::Return);
+
+    let recording = run_scenario("high_score_recorded_on_death", events);
+
+    let high_scores = find_fullscreen_text(&recording, "HIGH SCORES").expect("High scores were not shown");
+    assert!(high_scores.join(" ").contains("1. 100 points"));
+}
+
+#[test]
+fn morgue_files_are_not_overwritten() {
+    let user_path = user_dir("morgue_files_are_not_overwritten");
+    let language: Box<Language> = Box::new(languages::English);
+    let game_state = GameState::new();
+
+    let first = morgue_file::write(&user_path, &game_state, 0, &language).expect("Failed to write morgue file");
+    let second = morgue_file::write(&user_path, &game_state, 0, &language).expect("Failed to write morgue file");
+    let both_exist = first.is_file() && second.is_file();
+
+    fs::remove_dir_all(user_path).ok();
+
+    assert!(first != second);
+    assert!(both_exist);
+}
+
+#[test]
+fn corrupt_run_history_is_set_aside() {
+    let user_path = user_dir("corrupt_run_history_is_set_aside");
+    game_file::write_string(user_path.join(user_files::HISTORY), "not a history").expect("Failed to write history");
+
+    let recording = run_until_killed(&user_path);
+
+    let set_aside = game_file::read_string(user_path.join("history-corrupt.json"));
+    let history = run_history_file::load(&user_path);
+
+    fs::remove_dir_all(user_path).ok();
+
+    assert_eq!(set_aside.ok(), Some("not a history".to_string()));
+    assert_eq!(history.expect("Failed to load history").high_scores().len(), 1);
+    assert!(recording.frames().iter().any(|frame| frame.log_contains("The run history is corrupt.")));
+}
+
+fn first_menu_prelude(recording: &HeadlessRecording) -> String {
+    match recording.frames().front().and_then(|frame| frame.fullscreen.as_ref()) {
+        Some(&HeadlessFullscreen::Menu { ref prelude, ref items, .. }) => {
+            assert!(!items.iter().any(|item| item == "Continue"));
+            prelude.join(" ")
+        }
+        other => panic!("Expected main menu, found {:?}", other),
+    }
+}
+
+fn run_with_save(name: &str, save: Vec<u8>) -> HeadlessRecording {
+    let slot_path = save_slot::slot_path(user_dir(name), 1);
+    fs::create_dir_all(&slot_path).expect("Failed to create save slot");
+    File::create(slot_path.join(user_files::SAVE))
+        .and_then(|mut f| f.write_all(&save))
+        .expect("Failed to write save file");
+
+    // select "Quit", the last item in the main menu
+    run_scenario(name, vec![InputEvent::Up, InputEvent::Return])
+}
+
+/// The size of a serialized save header: a 4 byte version and an 8 byte checksum
+const SAVE_HEADER_SIZE: usize = 12;
+
+#[test]
+fn save_from_future_version() {
+    let mut save = save_file::SAVE_MAGIC.to_vec();
+    save.extend(vec![0xff; SAVE_HEADER_SIZE]);
+
+    let recording = run_with_save("save_from_future_version", save);
+    assert!(first_menu_prelude(&recording).contains("unsupported version"));
+}
+
+#[test]
+fn headerless_save_is_rejected() {
+    let user_path = user_dir("headerless_save_is_rejected");
+
+    let mut events = new_game();
+    events.push(InputEvent::Quit);
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    // saves were written without the magic number or header before the
+    // header was introduced
+    let save_path = save_slot::slot_path(&user_path, 1).join(user_files::SAVE);
+    let mut save = Vec::new();
+    File::open(&save_path).and_then(|mut f| f.read_to_end(&mut save)).expect("Failed to read save file");
+    let payload = save.split_off(save_file::SAVE_MAGIC.len() + SAVE_HEADER_SIZE);
+
+    fs::remove_dir_all(user_path).ok();
+
+    let recording = run_with_save("headerless_save_is_rejected", payload);
+    assert!(first_menu_prelude(&recording).contains("unsupported version (format 1)"));
+}
+
+#[test]
+fn corrupt_save() {
+    let mut save = save_file::SAVE_MAGIC.to_vec();
+    save.extend(vec![0; SAVE_HEADER_SIZE]);
+    save.extend(b"not a game state".iter().cloned());
+
+    let recording = run_with_save("corrupt_save", save);
+    assert!(first_menu_prelude(&recording).contains("corrupt"));
+}
+
+#[test]
+fn export_and_import_save() {
+    let user_path = user_dir("export_and_import_save");
+    let exported = user_path.join("save.json");
+
+    let mut events = new_game();
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Quit);
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    let slot_path = save_slot::slot_path(&user_path, 1);
+    save_file::export(&slot_path, &exported).expect("Failed to export save");
+    save_file::delete(&slot_path);
+    save_file::import(&slot_path, &exported).expect("Failed to import save");
+
+    // select "Continue" from the main menu
+    let recording = run_in_user_dir(&user_path, vec![InputEvent::Return, InputEvent::Quit], Arguments::default());
+
+    fs::remove_dir_all(user_path).ok();
+
+    let window = last_game_window(&recording);
+    assert_eq!(window.find_visible(TileType::Player), vec![Coord { x: START_COORD.x + 1, y: START_COORD.y }]);
+}
+
+#[test]
+fn new_game_in_second_slot() {
+    let user_path = user_dir("new_game_in_second_slot");
+
+    let mut events = new_game();
+    events.push(InputEvent::Quit);
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    // pause the resumed game, select "New Game", then pick the second slot
+    let events = vec![
+        InputEvent::Return,
+        InputEvent::Escape,
+        InputEvent::Down,
+        InputEvent::Return,
+        InputEvent::Down,
+        InputEvent::Return,
+        InputEvent::Space,
+        InputEvent::Quit,
+    ];
+    let recording = run_in_user_dir(&user_path, events, Arguments::default());
+
+    let slots_occupied: Vec<bool> = save_slot::slots().map(|slot| {
+        save_slot::slot_path(&user_path, slot).join(user_files::SAVE).is_file()
+    }).collect();
+
+    fs::remove_dir_all(user_path).ok();
+
+    assert_eq!(slots_occupied, vec![true, true, false]);
+
+    let picker_items = recording.frames().iter().filter_map(|frame| {
+        match frame.fullscreen {
+            Some(HeadlessFullscreen::Menu { ref items, .. }) if items.iter().any(|item| item.starts_with("Slot 1")) => Some(items.clone()),
+            _ => None,
+        }
+    }).next().expect("Slot picker was not shown");
+
+    assert!(picker_items[0].starts_with("Slot 1: Level 1"));
+    assert_eq!(picker_items[1], "Slot 2: (empty)");
+}
+
+#[test]
+fn legacy_save_moves_to_first_slot() {
+    let user_path = user_dir("legacy_save_moves_to_first_slot");
+    let game_path = user_path.join("game");
+
+    let mut events = new_game();
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Quit);
+    run_in_user_dir(&game_path, events, Arguments::default());
+
+    // saves used to be kept directly in the user directory
+    fs::rename(save_slot::slot_path(&game_path, 1).join(user_files::SAVE), user_path.join(user_files::SAVE))
+        .expect("Failed to move save file");
+
+    let profile_path = user_files::prepare_profile(&user_path, user_files::DEFAULT_PROFILE)
+        .expect("Failed to prepare profile");
+    let moved = !user_path.join(user_files::SAVE).exists();
+
+    // select "Continue" from the main menu
+    let recording = run_in_user_dir(&profile_path, vec![InputEvent::Return, InputEvent::Quit], Arguments::default());
+
+    fs::remove_dir_all(user_path).ok();
+
+    assert!(moved);
+
+    let window = last_game_window(&recording);
+    assert_eq!(window.find_visible(TileType::Player), vec![Coord { x: START_COORD.x + 1, y: START_COORD.y }]);
+}
+
+#[test]
+fn load_falls_back_to_backup() {
+    let user_path = user_dir("load_falls_back_to_backup");
+    let slot_path = save_slot::slot_path(&user_path, 1);
+
+    let mut events = new_game();
+    events.push(InputEvent::Right);
+    events.push(InputEvent::Quit);
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    // select "Continue" from the main menu, so the previous save becomes a backup
+    run_in_user_dir(&user_path, vec![InputEvent::Return, InputEvent::Right, InputEvent::Quit], Arguments::default());
+
+    // truncate the current save
+    File::create(slot_path.join(user_files::SAVE)).expect("Failed to truncate save file");
+
+    let recording = run_in_user_dir(&user_path, vec![InputEvent::Return, InputEvent::Quit], Arguments::default());
+
+    fs::remove_dir_all(user_path).ok();
+
+    let window = last_game_window(&recording);
+    assert_eq!(window.find_visible(TileType::Player), vec![Coord { x: START_COORD.x + 1, y: START_COORD.y }]);
+
+    let frame = recording.last().expect("No frames were published");
+    assert!(frame.log_contains("restored from a backup"));
+}
+
+#[test]
+fn one_daily_challenge_per_day() {
+    let user_path = user_dir("one_daily_challenge_per_day");
+
+    // select "Daily Challenge", pick the first slot, dismiss the intro, then quit
+    let events = vec![
+        InputEvent::Down,
+        InputEvent::Return,
+        InputEvent::Return,
+        InputEvent::Space,
+        InputEvent::Quit,
+    ];
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    // select "Daily Challenge" again, dismiss the leaderboard, then resume and quit the saved game
+    let events = vec![
+        InputEvent::Down,
+        InputEvent::Down,
+        InputEvent::Return,
+        InputEvent::Return,
+        InputEvent::Escape,
+        InputEvent::Quit,
+    ];
+    let recording = run_in_user_dir(&user_path, events, Arguments::default());
+
+    let leaderboard = daily_file::load(&user_path).expect("Failed to load daily leaderboard");
+
+    fs::remove_dir_all(user_path).ok();
+
+    assert!(leaderboard.has_attempted(daily_challenge_day(), user_files::DEFAULT_PROFILE));
+    assert!(recording.frames().iter().any(|frame| {
+        match frame.fullscreen {
+            Some(HeadlessFullscreen::Text(ref lines)) => lines.iter().any(|line| line.contains("already attempted")),
+            _ => false,
+        }
+    }));
+}
+
+#[test]
+fn new_game_after_daily_challenge_uses_its_own_seed() {
+    let user_path = user_dir("new_game_after_daily_challenge_uses_its_own_seed");
+
+    // start the daily challenge in the first slot and pause it, then select
+    // "New Game", pick the second slot, dismiss the intro, and quit
+    let events = vec![
+        InputEvent::Down,
+        InputEvent::Return,
+        InputEvent::Return,
+        InputEvent::Space,
+        InputEvent::Escape,
+        InputEvent::Down,
+        InputEvent::Return,
+        InputEvent::Down,
+        InputEvent::Return,
+        InputEvent::Space,
+        InputEvent::Quit,
+    ];
+    run_in_user_dir(&user_path, events, Arguments::default());
+
+    let seeds: Vec<Option<usize>> = save_slot::slots().map(|slot| {
+        save_file::load(save_slot::slot_path(&user_path, slot)).ok().map(|(game_state, _)| game_state.seed())
+    }).collect();
+
+    fs::remove_dir_all(user_path).ok();
+
+    assert_eq!(seeds, vec![Some(daily_challenge_seed(daily_challenge_day())), Some(SEED), None]);
+}
+
+#[test]
+fn same_input_gives_same_state() {
+    let user_path = user_dir("same_input_gives_same_state");
+    let trace_path = user_path.join("trace");
+
+    let mut events = new_game();
+    let fire_index = events.len();
+    events.push(InputEvent::Char('f'));
+    events.push(InputEvent::Return);
+    for _ in 0..10 {
+        events.push(InputEvent::Char('.'));
+    }
+    events.push(InputEvent::Quit);
+
+    let mut args = Arguments::default();
+    args.state_trace_path = Some(trace_path.clone());
+    assert!(check_state_in_user_dir(&user_path.join("a"), events.clone(), args).is_none());
+
+    let reference = state_trace_file::load(&trace_path).expect("Failed to load state trace");
+    assert!(!reference.is_empty());
+
+    let mut args = Arguments::default();
+    args.state_reference = Some(reference.clone());
+    let divergence = check_state_in_user_dir(&user_path.join("b"), events.clone(), args);
+    assert!(divergence.is_none(), "{}", divergence.unwrap());
+
+    // waiting instead of aiming and firing leaves a different game state
+    events[fire_index] = InputEvent::Char('.');
+    events[fire_index + 1] = InputEvent::Char('.');
+
+    let mut args = Arguments::default();
+    args.state_reference = Some(reference);
+    let divergence = check_state_in_user_dir(&user_path.join("c"), events, args);
+
+    fs::remove_dir_all(user_path).ok();
+
+    assert!(divergence.is_some());
+}
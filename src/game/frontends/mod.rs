@@ -3,3 +3,5 @@ pub mod ansi;
 
 #[cfg(feature = "sdl2")]
 pub mod sdl;
+
+pub mod headless;
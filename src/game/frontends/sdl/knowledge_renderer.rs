@@ -319,6 +319,23 @@ impl<'a, 'b> SdlKnowledgeRendererInternal<'a, 'b> {
         self.sdl_renderer.fill_rect(self.hud_rect).expect("Failed to clear hud");
     }
 
+    /// Draws text in the hud starting at a horizontal offset in pixels,
+    /// returning the offset following the text
+    fn draw_hud_text(&mut self, text: &str, cursor: usize, colour: Color) -> usize {
+        let surface = self.font.render(text).solid(colour).expect("Failed to create text surface");
+        let texture = self.sdl_renderer.create_texture_from_surface(&surface).expect("Failed to create text texture");
+
+        let text_width = text.chars().count() * self.hud_height_px(); // square fonts
+        let text_rect = Rect::new((self.hud_position.x + cursor as isize) as i32,
+                                  (self.hud_position.y + HUD_TOP_PADDING_PX as isize) as i32,
+                                  text_width as u32,
+                                  self.hud_height_px() as u32);
+
+        self.sdl_renderer.copy(&texture, None, Some(text_rect)).expect("Failed to render text");
+
+        cursor + text_width
+    }
+
     fn clear_message_log(&mut self) {
         self.sdl_renderer.set_draw_color(self.clear_colour);
         self.sdl_renderer.fill_rect(self.message_log_rect).expect("Failed to clear message_log");
@@ -673,16 +690,23 @@ impl<'a, 'b> KnowledgeRenderer for SdlKnowledgeRenderer<'a, 'b> {
         let hit_points = entity.hit_points().expect("Entity missing hit_points");
 
         let health_text = format!("{}/{}", hit_points.current(), hit_points.max());
-        let surface = self.renderer.font.render(health_text.as_ref()).solid(sdl_colour).expect("Failed to create text surface");
-        let texture = self.renderer.sdl_renderer.create_texture_from_surface(&surface).expect("Failed to create text texture");
+        cursor = self.renderer.draw_hud_text(health_text.as_ref(), cursor, sdl_colour);
 
-        let text_width = health_text.len() * self.renderer.hud_height_px(); // square fonts
-        let text_rect = Rect::new((self.renderer.hud_position.x + cursor as isize) as i32,
-                                  (self.renderer.hud_position.y + HUD_TOP_PADDING_PX as isize) as i32,
-                                  text_width as u32,
-                                  self.renderer.hud_height_px() as u32);
+        if let Some(magazine) = entity.magazine() {
+            cursor += self.renderer.hud_padded_height_px();
+
+            let ammo_text = format!("Ammo {}/{} ({})", magazine.loaded(), magazine.capacity(), magazine.reserve());
+            cursor = self.renderer.draw_hud_text(ammo_text.as_ref(), cursor, sdl_colour);
+        }
 
-        self.renderer.sdl_renderer.copy(&texture, None, Some(text_rect)).expect("Failed to render text");
+        if let Some(status_effects) = entity.status_effects() {
+            for effect in status_effects.iter() {
+                cursor += self.renderer.hud_padded_height_px();
+
+                let effect_text = format!("{} {}", effect.effect_type.short_name(), effect.duration);
+                cursor = self.renderer.draw_hud_text(effect_text.as_ref(), cursor, sdl_colour);
+            }
+        }
     }
 
     fn fullscreen_menu<T>(&mut self, prelude: Option<MessageType>, menu: &SelectMenu<T>, state: &SelectMenuState, language: &Box<Language>) {
@@ -75,15 +75,7 @@ pub fn launch(args: Arguments) -> ExternalResult<()> {
 
     let input = frontends::sdl::SdlInputSource::new(sdl.clone());
 
-    let mut game = GameCtx::new(renderer,
-                                input,
-                                args.rng_seed,
-                                GAME_WIDTH,
-                                GAME_HEIGHT);
-
-    game.run(args)?;
-
-    Ok(())
+    run_game(renderer, input, args)
 }
 
 fn parse_tileset_spec<P: AsRef<path::Path>>(resource_path: P) -> Option<(toml::value::Table, path::PathBuf)> {
@@ -6,6 +6,7 @@ use std::io::{Read, Write};
 use serde::ser::Serialize;
 use serde::de::Deserialize;
 use toml;
+use serde_json;
 
 #[derive(Debug, Clone, Copy)]
 pub enum FileError {
@@ -30,6 +31,11 @@ pub fn read_toml<P: AsRef<path::Path>, T: Deserialize>(path: P) -> FileResult<T>
     toml::from_str(&s).map_err(|_| FileError::InvalidFormat)
 }
 
+pub fn read_json<P: AsRef<path::Path>, T: Deserialize>(path: P) -> FileResult<T> {
+    let s = read_string(path)?;
+    serde_json::from_str(&s).map_err(|_| FileError::InvalidFormat)
+}
+
 pub fn write_string<P: AsRef<path::Path>, S: AsRef<str>>(path: P, string: S) -> FileResult<()> {
     let bytes = string.as_ref().as_bytes();
     File::create(path).and_then(|mut f| f.write_all(bytes))
@@ -41,3 +47,8 @@ pub fn write_toml<P: AsRef<path::Path>, T: Serialize>(path: P, data: &T) -> File
     let string = toml::to_string(data).map_err(|_| FileError::InvalidFormat)?;
     write_string(path, string)
 }
+
+pub fn write_json<P: AsRef<path::Path>, T: Serialize>(path: P, data: &T) -> FileResult<()> {
+    let string = serde_json::to_string_pretty(data).map_err(|_| FileError::InvalidFormat)?;
+    write_string(path, string)
+}
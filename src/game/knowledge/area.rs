@@ -0,0 +1,33 @@
+/// Knowledge consisting only of which cells have been seen
+
+use coord::Coord;
+use game::*;
+use spatial_hash::*;
+
+pub struct VisibleArea {
+    coords: Vec<Coord>,
+}
+
+impl VisibleArea {
+    pub fn new() -> Self {
+        VisibleArea {
+            coords: Vec::new(),
+        }
+    }
+
+    pub fn coords(&self) -> &[Coord] {
+        &self.coords
+    }
+}
+
+impl LevelKnowledge for VisibleArea {
+    fn update_cell(&mut self, coord: Coord, _world_cell: &SpatialHashCell, accuracy: f64, _action_env: ActionEnv) -> bool {
+        // cells on the boundaries between octants are reported more than once
+        if accuracy > 0.0 && !self.coords.contains(&coord) {
+            self.coords.push(coord);
+            true
+        } else {
+            false
+        }
+    }
+}
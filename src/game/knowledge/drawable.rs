@@ -4,6 +4,7 @@ use spatial_hash::*;
 use grid::{Grid, StaticGrid, DefaultGrid};
 use util::{BestMap, TwoDimensionalCons};
 use coord::Coord;
+use search::TraverseCost;
 
 pub type DrawableKnowledge = GameKnowledge<DrawableKnowledgeLevel>;
 
@@ -15,6 +16,8 @@ pub struct DrawableKnowledgeCell {
     you_see: BestMap<isize, YouSeeMessageType>,
     description: BestMap<isize, DescriptionMessageType>,
     tear: bool,
+    solid: bool,
+    door: bool,
     health_overlay: BestMap<isize, HitPoints>,
 }
 
@@ -27,6 +30,8 @@ impl DrawableKnowledgeCell {
             you_see: BestMap::new(),
             description: BestMap::new(),
             tear: false,
+            solid: false,
+            door: false,
             health_overlay: BestMap::new(),
         }
     }
@@ -66,6 +71,8 @@ impl DrawableKnowledgeCell {
         if self.last_updated <= world_cell.last_updated() {
 
             self.tear = world_cell.tear();
+            self.solid = world_cell.solid();
+            self.door = world_cell.any_door().is_some();
             self.foreground.clear();
             self.background.clear();
             self.you_see.clear();
@@ -107,12 +114,24 @@ impl Default for DrawableKnowledgeCell {
     }
 }
 
+impl TraverseCost for DrawableKnowledgeCell {
+    fn traverse_cost(&self) -> Option<f64> {
+        // closed doors are solid, but are opened by walking into them
+        if self.solid && !self.door {
+            None
+        } else {
+            Some(1.0)
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct DrawableKnowledgeLevel {
     grid: StaticGrid<DrawableKnowledgeCell>,
     default: DrawableKnowledgeCell,
     targets: Vec<Coord>,
     last_action_id: u64,
+    num_explored: usize,
 }
 
 impl DrawableKnowledgeLevel {
@@ -120,6 +139,10 @@ impl DrawableKnowledgeLevel {
         self.grid.get(coord).unwrap_or_else(|| &self.default)
     }
 
+    pub fn grid(&self) -> &StaticGrid<DrawableKnowledgeCell> {
+        &self.grid
+    }
+
     pub fn sort_targets(&mut self, position: Coord) -> &[Coord] {
         self.targets.sort_by(|a, b| a.squared_distance(position).cmp(&b.squared_distance(position)));
         self.targets.as_slice()
@@ -140,6 +163,11 @@ impl DrawableKnowledgeLevel {
     pub fn height(&self) -> usize {
         self.grid.height()
     }
+
+    /// Number of cells which have been seen at least once
+    pub fn num_explored(&self) -> usize {
+        self.num_explored
+    }
 }
 
 impl LevelKnowledge for DrawableKnowledgeLevel {
@@ -152,6 +180,10 @@ impl LevelKnowledge for DrawableKnowledgeLevel {
                 return false;
             }
 
+            if knowledge_cell.last_updated == 0 {
+                self.num_explored += 1;
+            }
+
             let change = knowledge_cell.update(world_cell, accuracy, action_env);
 
             if self.last_action_id != action_env.id {
@@ -177,10 +209,18 @@ impl TwoDimensionalCons for DrawableKnowledgeLevel {
             default: DrawableKnowledgeCell::new(),
             targets: Vec::new(),
             last_action_id: 0,
+            num_explored: 0,
         }
     }
 }
 
+impl DrawableKnowledge {
+    /// Number of cells which have been seen at least once, across all levels
+    pub fn num_explored(&self) -> usize {
+        self.level_iter().map(DrawableKnowledgeLevel::num_explored).sum()
+    }
+}
+
 pub struct CellDrawInfo {
     pub foreground: Option<TileType>,
     pub background: Option<TileType>,
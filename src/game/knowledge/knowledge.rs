@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{btree_map, BTreeMap};
 
 use game::*;
 use spatial_hash::*;
@@ -31,6 +31,10 @@ impl<K: LevelKnowledge> GameKnowledge<K> {
     pub fn level_mut(&mut self, level_id: LevelId) -> &mut K {
         self.levels.get_mut(&level_id).expect("No such level")
     }
+
+    pub fn level_iter(&self) -> btree_map::Values<LevelId, K> {
+        self.levels.values()
+    }
 }
 
 impl<K: LevelKnowledge + Default> Default for GameKnowledge<K> {
@@ -1,11 +1,12 @@
 use std::collections::HashSet;
+use std::collections::hash_set;
 
 use game::*;
 use spatial_hash::*;
 use grid::{Grid, StaticGrid, DefaultGrid};
 use coord::Coord;
 use search::TraverseCost;
-use util::TwoDimensionalCons;
+use util::{TwoDimensionalCons, serialize_unordered};
 
 pub type SimpleNpcKnowledge = GameKnowledge<SimpleNpcKnowledgeLevel>;
 
@@ -13,6 +14,8 @@ pub type SimpleNpcKnowledge = GameKnowledge<SimpleNpcKnowledgeLevel>;
 pub struct SimpleNpcKnowledgeCell {
     last_updated: u64,
     solid: bool,
+    pc: bool,
+    enemy: bool,
 }
 
 impl SimpleNpcKnowledgeCell {
@@ -20,9 +23,25 @@ impl SimpleNpcKnowledgeCell {
         SimpleNpcKnowledgeCell {
             last_updated: 0,
             solid: false,
+            pc: false,
+            enemy: false,
         }
     }
 
+    pub fn solid(&self) -> bool {
+        self.solid
+    }
+
+    /// True if the player was in this cell when it was last seen
+    pub fn pc(&self) -> bool {
+        self.pc
+    }
+
+    /// True if an enemy was in this cell when it was last seen
+    pub fn enemy(&self) -> bool {
+        self.enemy
+    }
+
     fn update(&mut self, world_cell: &SpatialHashCell, _accuracy: f64, action_env: ActionEnv) -> bool {
         let mut changed = false;
 
@@ -30,6 +49,8 @@ impl SimpleNpcKnowledgeCell {
             changed = true;
 
             self.solid = world_cell.solid();
+            self.pc = world_cell.pc();
+            self.enemy = world_cell.enemy();
         }
 
         self.last_updated = action_env.id;
@@ -57,6 +78,7 @@ impl TraverseCost for SimpleNpcKnowledgeCell {
 #[derive(Serialize, Deserialize)]
 pub struct SimpleNpcKnowledgeLevel {
     grid: StaticGrid<SimpleNpcKnowledgeCell>,
+    #[serde(serialize_with = "serialize_unordered")]
     targets: HashSet<Coord>,
     latest_target: u64,
     default: SimpleNpcKnowledgeCell,
@@ -78,6 +100,10 @@ impl SimpleNpcKnowledgeLevel {
     pub fn contains_target(&self, coord: Coord) -> bool {
         self.targets.contains(&coord)
     }
+
+    pub fn targets(&self) -> hash_set::Iter<Coord> {
+        self.targets.iter()
+    }
 }
 
 impl LevelKnowledge for SimpleNpcKnowledgeLevel {
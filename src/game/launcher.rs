@@ -1,3 +1,6 @@
+use std::fs;
+use std::path;
+
 use game::*;
 
 pub const GAME_WIDTH: usize = 41;
@@ -5,6 +8,10 @@ pub const GAME_HEIGHT: usize = 26;
 
 pub fn launch(args: Arguments) -> ExternalResult<()> {
 
+    if let Some(ref transfer) = args.save_transfer {
+        return transfer_save(&save_slot::slot_path(&args.user_path, args.save_slot), transfer);
+    }
+
     match args.frontend {
         Frontend::Ansi => {
 
@@ -26,3 +33,100 @@ pub fn launch(args: Arguments) -> ExternalResult<()> {
         }
     }
 }
+
+fn transfer_save(slot_path: &path::Path, transfer: &SaveTransfer) -> ExternalResult<()> {
+    match *transfer {
+        SaveTransfer::Export(ref path) => {
+            let restored_from = save_file::export(slot_path, path)
+                .map_err(|e| format!("Failed to export save to {}: {:?}", path.display(), e))?;
+            if let Some(error) = restored_from {
+                println!("The current save could not be read ({:?}), so the most recent backup was exported", error);
+            }
+            println!("Exported save to {}", path.display());
+        }
+        SaveTransfer::Import(ref path) => {
+            save_file::import(slot_path, path)
+                .map_err(|e| format!("Failed to import save from {}: {:?}", path.display(), e))?;
+            println!("Imported save from {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Runs a game with the given renderer and input source. If a replay was
+/// specified, it is played back in a separate user directory. Otherwise the
+/// session is recorded to a new replay file in the user directory. If the game
+/// state was checked against a reference trace, any divergence is reported
+/// as an error.
+pub fn run_game<R: KnowledgeRenderer, I: 'static + InputSource + Clone>(renderer: R,
+                                                                        input_source: I,
+                                                                        mut args: Arguments) -> ExternalResult<()> {
+
+    let state_trace = state_trace(&mut args)?;
+
+    if let Some(replay) = args.replay.take() {
+        let Replay { header, events } = replay;
+
+        let session_path = args.user_path.join(user_files::REPLAY_SESSION);
+        replay_file::prepare_session(&header, &session_path)
+            .map_err(|e| format!("Failed to prepare replay session: {:?}", e))?;
+
+        let replay_input_source = ReplayInputSource::new(events, clock_for_speed(args.replay_speed), input_source);
+
+        args.user_path = session_path.clone();
+        args.shared_user_path = session_path.clone();
+
+        // play back with the settings the replay was recorded with
+        args.debug = header.debug;
+        args.config = GameConfig::from_file(session_path.join(user_files::CONFIG)).unwrap_or_default();
+
+        let mut game = GameCtx::new(renderer, replay_input_source, header.seed, GAME_WIDTH, GAME_HEIGHT);
+        game.set_state_trace(state_trace);
+        game.set_daily_challenge_day(header.daily_challenge_day);
+        // animations are sped up along with the rest of the replay
+        game.set_clock(clock_for_speed(args.animation_speed * args.replay_speed));
+
+        let result = game.run(args);
+
+        let _ = fs::remove_dir_all(session_path);
+
+        result?;
+        check_state_divergence(&game)?;
+    } else {
+        let header = replay_file::header(&args.user_path, &args.shared_user_path, args.rng_seed, args.debug);
+        let writer = replay_file::ReplayWriter::create(&args.user_path, &header).ok();
+
+        let recording_input_source = RecordingInputSource::new(input_source, writer);
+
+        let mut game = GameCtx::new(renderer, recording_input_source, args.rng_seed, GAME_WIDTH, GAME_HEIGHT);
+        game.set_state_trace(state_trace);
+        game.set_daily_challenge_day(header.daily_challenge_day);
+        game.set_clock(clock_for_speed(args.animation_speed));
+
+        game.run(args)?;
+        check_state_divergence(&game)?;
+    }
+
+    Ok(())
+}
+
+fn state_trace(args: &mut Arguments) -> ExternalResult<StateTrace> {
+    let writer = if let Some(ref path) = args.state_trace_path {
+        let writer = state_trace_file::StateTraceWriter::create(path)
+            .map_err(|e| format!("Failed to create state trace {}: {:?}", path.display(), e))?;
+        Some(writer)
+    } else {
+        None
+    };
+
+    Ok(StateTrace::new(writer, args.state_reference.take()))
+}
+
+fn check_state_divergence<R: KnowledgeRenderer, I: 'static + InputSource + Clone>(game: &GameCtx<R, I>) -> ExternalResult<()> {
+    if let Some(divergence) = game.state_divergence() {
+        Err(divergence.to_string())
+    } else {
+        Ok(())
+    }
+}
@@ -55,6 +55,10 @@ impl LevelTable {
         index
     }
 
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
     pub fn level(&self, level_id: LevelId) -> &Level {
         self.levels.get(level_id).expect("No such level")
     }
@@ -1,7 +1,14 @@
-use game::{MessageType, Message};
+use game::{MessageType, Message, RunRecord, DailyResult};
+use game::data::CharacterStats;
 
 pub trait Language {
     fn translate_repeated(&self, message_type: MessageType, repeated: usize, message: &mut Message);
+    /// Describes a finished game as a line of the high score table
+    fn translate_run(&self, rank: usize, record: &RunRecord, message: &mut Message);
+    /// Describes a daily challenge result as a line of the daily leaderboard
+    fn translate_daily_result(&self, rank: usize, result: &DailyResult, message: &mut Message);
+    /// Describes a character's statistics, one per line
+    fn translate_character_stats(&self, stats: &CharacterStats, message: &mut Message);
     fn translate(&self, message_type: MessageType, message: &mut Message) {
         self.translate_repeated(message_type, 1, message);
     }
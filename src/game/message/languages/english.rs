@@ -1,9 +1,30 @@
 use game::*;
+use game::data::{CharacterStats, ProjectileType, StatusEffectType};
 use colour::*;
+use game::save_file::SaveFileError;
+use game::save_slot::SaveSlotSummary;
 
 pub struct English;
 
 impl English {
+    fn translate_run_summary(&self, record: &RunRecord, message: &mut Message) {
+        message.push(MessagePart::Text(TextMessagePart::Plain(
+            format!("{} points: level {}, {} turns, {} kills. ", record.score, record.depth, record.turns, record.kills))));
+
+        match record.outcome {
+            RunOutcome::Died => {
+                let text = match record.cause_of_death {
+                    Some(DeathCause::Killer(tile)) => format!("Killed by {}. ", tile_name(tile)),
+                    Some(DeathCause::Bleeding) => "Bled to death. ".to_string(),
+                    Some(DeathCause::Explosion) => "Killed by an explosion. ".to_string(),
+                    Some(DeathCause::Impact) => "Knocked into something. ".to_string(),
+                    None => "Died. ".to_string(),
+                };
+                message.push(MessagePart::colour(colours::RED, &text));
+            }
+        }
+    }
+
     fn translate_you_see(&self, name: YouSeeMessageType, message: &mut Message) {
         match name {
             YouSeeMessageType::Player => {
@@ -23,9 +44,79 @@ impl English {
             ActionMessageType::PlayerCloseDoor => {
                 message.push(MessagePart::plain("I close the door."));
             }
+            ActionMessageType::PlayerReload => {
+                message.push(MessagePart::plain("I reload."));
+            }
+            ActionMessageType::PlayerMagazineFull => {
+                message.push(MessagePart::plain("My gun is already loaded."));
+            }
+            ActionMessageType::PlayerOutOfAmmo => {
+                message.push(MessagePart::plain("I have no bullets left."));
+            }
+            ActionMessageType::PlayerClickEmpty => {
+                message.push(MessagePart::plain("Click. Empty."));
+            }
+            ActionMessageType::Explosion => {
+                message.push(MessagePart::plain("The round explodes!"));
+            }
+            ActionMessageType::Hit(attacker, victim) => {
+                self.translate_attack(attacker, "hit", "hits", victim, ".", message);
+            }
+            ActionMessageType::CriticalHit(attacker, victim) => {
+                self.translate_attack(attacker, "critically hit", "critically hits", victim, "!", message);
+            }
+            ActionMessageType::Miss(attacker, victim) => {
+                self.translate_attack(attacker, "miss", "misses", victim, ".", message);
+            }
+            ActionMessageType::NoEffect(attacker, victim) => {
+                self.translate_attack(attacker, "hit", "hits", victim, ", to no effect.", message);
+            }
+            ActionMessageType::StatusEffectStart(tile, effect_type) => {
+                self.translate_status_effect(tile, "", effect_type, message);
+            }
+            ActionMessageType::StatusEffectEnd(tile, effect_type) => {
+                self.translate_status_effect(tile, "no longer ", effect_type, message);
+            }
+            ActionMessageType::TooFrightenedToAttack(tile) => {
+                let text = if tile == TileType::Player {
+                    "I am too frightened to attack.".to_string()
+                } else {
+                    format!("{} is too frightened to attack.", capitalise(tile_definite_name(tile)))
+                };
+                message.push(MessagePart::Text(TextMessagePart::Plain(text)));
+            }
+            ActionMessageType::Destroyed(tile) => {
+                let text = format!("{} is destroyed.", capitalise(tile_definite_name(tile)));
+                message.push(MessagePart::Text(TextMessagePart::Plain(text)));
+            }
         }
     }
 
+    fn translate_status_effect(&self, tile: TileType, qualifier: &str,
+                               effect_type: StatusEffectType, message: &mut Message) {
+        let state = status_effect_name(effect_type);
+        let text = if tile == TileType::Player {
+            format!("I am {}{}.", qualifier, state)
+        } else {
+            format!("{} is {}{}.", capitalise(tile_definite_name(tile)), qualifier, state)
+        };
+
+        message.push(MessagePart::Text(TextMessagePart::Plain(text)));
+    }
+
+    fn translate_attack(&self, attacker: TileType, first_person_verb: &str, third_person_verb: &str,
+                        victim: TileType, end: &str, message: &mut Message) {
+        let text = if attacker == TileType::Player {
+            format!("I {} {}{}", first_person_verb, tile_definite_name(victim), end)
+        } else if victim == TileType::Player {
+            format!("{} {} me{}", capitalise(tile_definite_name(attacker)), third_person_verb, end)
+        } else {
+            format!("{} {} {}{}", capitalise(tile_definite_name(attacker)), third_person_verb, tile_definite_name(victim), end)
+        };
+
+        message.push(MessagePart::Text(TextMessagePart::Plain(text)));
+    }
+
     fn translate_description(&self, description: DescriptionMessageType, message: &mut Message) {
         match description {
             DescriptionMessageType::Player => {
@@ -39,6 +130,12 @@ impl English {
             MenuMessageType::NewGame => {
                 message.push(MessagePart::plain("New Game"));
             }
+            MenuMessageType::DailyChallenge => {
+                message.push(MessagePart::plain("Daily Challenge"));
+            }
+            MenuMessageType::LoadGame => {
+                message.push(MessagePart::plain("Load Game"));
+            }
             MenuMessageType::Quit => {
                 message.push(MessagePart::plain("Quit"));
             }
@@ -51,6 +148,9 @@ impl English {
             MenuMessageType::Controls => {
                 message.push(MessagePart::plain("Controls"));
             }
+            MenuMessageType::HighScores => {
+                message.push(MessagePart::plain("High Scores"));
+            }
             MenuMessageType::Control(input, control) => {
                 message.push(MessagePart::Text(TextMessagePart::Plain(String::from(control))));
                 message.push(MessagePart::plain(": "));
@@ -64,9 +164,51 @@ impl English {
                 message.push(MessagePart::Text(TextMessagePart::Plain(String::from(control))));
                 message.push(MessagePart::plain(": press a key..."));
             }
+            MenuMessageType::SaveSlot(slot, summary) => {
+                message.push(MessagePart::Text(TextMessagePart::Plain(format!("Slot {}: ", slot))));
+                match summary {
+                    SaveSlotSummary::Empty => {
+                        message.push(MessagePart::plain("(empty)"));
+                    }
+                    SaveSlotSummary::Saved(info) => {
+                        message.push(MessagePart::Text(TextMessagePart::Plain(
+                            format!("Level {}, {} turns, {}", info.level_id + 1, info.pc_turns, UtcDateTime::from_unix(info.last_played)))));
+                    }
+                    SaveSlotSummary::SavedWithoutInfo => {
+                        message.push(MessagePart::plain("(saved game)"));
+                    }
+                }
+            }
         }
     }
 
+    fn translate_save_file_error(&self, error: SaveFileError, message: &mut Message) {
+        match error {
+            SaveFileError::MissingFile | SaveFileError::Unreadable => {
+                message.push(MessagePart::plain("The save file could not be read. "));
+            }
+            SaveFileError::NotASaveFile => {
+                message.push(MessagePart::plain("The save file is not recognised. "));
+            }
+            SaveFileError::UnsupportedVersion(version) => {
+                message.push(MessagePart::Text(TextMessagePart::Plain(
+                    format!("The save file is from an unsupported version (format {}). ", version))));
+            }
+            SaveFileError::ChecksumMismatch | SaveFileError::InvalidData => {
+                message.push(MessagePart::plain("The save file is corrupt. "));
+            }
+            SaveFileError::FailedToWrite => {
+                message.push(MessagePart::plain("The save file could not be written. "));
+            }
+        }
+    }
+
+    fn translate_save_load_failed(&self, error: SaveFileError, message: &mut Message) {
+        message.push(MessagePart::colour(colours::RED, "Unable to load saved game. "));
+        self.translate_save_file_error(error, message);
+        message.push(MessagePart::plain("Starting a new game will replace it."));
+    }
+
     fn translate_intro(&self, message: &mut Message) {
         message.push(MessagePart::plain("Everything beneath the moonlight appears different. "));
         message.push(MessagePart::plain("An arcane tome is rumored to be hidden somewhere in the forest. "));
@@ -74,7 +216,105 @@ impl English {
     }
 }
 
+fn tile_name(tile: TileType) -> &'static str {
+    match tile {
+        TileType::Player => "yourself",
+        TileType::TerrorPillar => "a terror pillar",
+        TileType::TerrorFly => "a terror fly",
+        TileType::Hunter => "a hunter",
+        TileType::Tree | TileType::DeadTree => "a tree",
+        TileType::ClosedDoor | TileType::OpenDoor => "a door",
+        TileType::Wall | TileType::CrackedWall => "a wall",
+        TileType::Bullet => "a bullet",
+        TileType::Pellet => "a pellet",
+        TileType::PiercingRound => "a piercing round",
+        TileType::ExplosiveRound => "an explosive round",
+        TileType::SilverBullet => "a silver bullet",
+        _ => "something",
+    }
+}
+
+fn tile_definite_name(tile: TileType) -> &'static str {
+    match tile {
+        TileType::Player => "myself",
+        TileType::TerrorPillar => "the terror pillar",
+        TileType::TerrorFly => "the terror fly",
+        TileType::Hunter => "the hunter",
+        TileType::Tree | TileType::DeadTree => "the tree",
+        TileType::ClosedDoor | TileType::OpenDoor => "the door",
+        TileType::Wall | TileType::CrackedWall => "the wall",
+        TileType::Bullet => "the bullet",
+        TileType::Pellet => "the pellet",
+        TileType::PiercingRound => "the piercing round",
+        TileType::ExplosiveRound => "the explosive round",
+        TileType::SilverBullet => "the silver bullet",
+        _ => "something",
+    }
+}
+
+fn projectile_type_name(projectile_type: ProjectileType) -> &'static str {
+    match projectile_type {
+        ProjectileType::Bullet => "bullet",
+        ProjectileType::Spread => "spread shot",
+        ProjectileType::Piercing => "piercing round",
+        ProjectileType::Explosive => "explosive round",
+        ProjectileType::Silver => "silver bullet",
+    }
+}
+
+fn status_effect_name(effect_type: StatusEffectType) -> &'static str {
+    match effect_type {
+        StatusEffectType::Bleeding => "bleeding",
+        StatusEffectType::Stunned => "stunned",
+        StatusEffectType::Slowed => "slowed",
+        StatusEffectType::Terrified => "terrified",
+    }
+}
+
+fn capitalise(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
 impl Language for English {
+    fn translate_run(&self, rank: usize, record: &RunRecord, message: &mut Message) {
+        message.push(MessagePart::Text(TextMessagePart::Plain(format!("{}. ", rank))));
+        self.translate_run_summary(record, message);
+        message.push(MessagePart::Text(TextMessagePart::Plain(
+            format!("Seed {}, {}", record.seed, UtcDateTime::from_unix(record.finished)))));
+    }
+
+    fn translate_daily_result(&self, rank: usize, result: &DailyResult, message: &mut Message) {
+        message.push(MessagePart::Text(TextMessagePart::Plain(format!("{}. {}: ", rank, result.profile))));
+        self.translate_run_summary(&result.record, message);
+    }
+
+    fn translate_character_stats(&self, stats: &CharacterStats, message: &mut Message) {
+        let lines = [
+            ("Kills", stats.kills),
+            ("Damage dealt", stats.damage_dealt),
+            ("Damage taken", stats.damage_taken),
+            ("Bullets fired", stats.bullets_fired),
+            ("Bullets hit", stats.bullets_hit),
+            ("Doors opened", stats.doors_opened),
+            ("Tiles explored", stats.tiles_explored),
+            ("Levels entered", stats.levels_entered),
+        ];
+
+        for &(name, value) in lines.iter() {
+            message.push(MessagePart::Text(TextMessagePart::Plain(format!("{}: {}", name, value))));
+            message.push(MessagePart::Newline);
+        }
+
+        if let Some(accuracy) = stats.accuracy() {
+            message.push(MessagePart::Text(TextMessagePart::Plain(format!("Accuracy: {:.0}%", accuracy * 100.0))));
+            message.push(MessagePart::Newline);
+        }
+    }
+
     fn translate_repeated(&self, message_type: MessageType, repeated: usize, message: &mut Message) {
 
         match message_type {
@@ -120,6 +360,55 @@ impl Language for English {
             MessageType::Menu(menu_message) => {
                 self.translate_menu(menu_message, message);
             }
+            MessageType::HighScores => {
+                message.push(MessagePart::colour(colours::PURPLE, "HIGH SCORES"));
+            }
+            MessageType::RunHistoryCorrupt => {
+                message.push(MessagePart::colour(colours::RED, "The run history is corrupt. "));
+                message.push(MessagePart::plain("It has been set aside and a new history started."));
+            }
+            MessageType::RunHistoryUnreadable => {
+                message.push(MessagePart::colour(colours::RED, "The run history could not be read."));
+            }
+            MessageType::NoRuns => {
+                message.push(MessagePart::plain("No games have been finished yet."));
+            }
+            MessageType::DailyLeaderboard(day) => {
+                let date = daily_challenge_date(day);
+                message.push(MessagePart::colour(colours::PURPLE,
+                    &format!("DAILY CHALLENGE {:04}-{:02}-{:02}", date.year, date.month, date.day)));
+            }
+            MessageType::DailyAlreadyAttempted => {
+                message.push(MessagePart::plain("You have already attempted today's challenge. "));
+                message.push(MessagePart::plain("A new challenge begins at midnight UTC."));
+            }
+            MessageType::CharacterStats => {
+                message.push(MessagePart::colour(colours::PURPLE, "STATISTICS"));
+            }
+            MessageType::RuleTrace => {
+                message.push(MessagePart::colour(colours::PURPLE, "RULE TRACE"));
+            }
+            MessageType::RuleTraceEmpty => {
+                message.push(MessagePart::plain("No rules have been traced. Rules are only traced in debug mode."));
+            }
+            MessageType::SelectedProjectile(projectile_type) => {
+                message.push(MessagePart::plain("Firing: "));
+                message.push(MessagePart::plain(projectile_type_name(projectile_type)));
+            }
+            MessageType::ChooseSaveSlot => {
+                message.push(MessagePart::plain("Choose a save slot"));
+            }
+            MessageType::SaveLoadFailed(error) => {
+                self.translate_save_load_failed(error, message);
+            }
+            MessageType::RestoredFromBackup(error) => {
+                self.translate_save_file_error(error, message);
+                message.push(MessagePart::plain("The game was restored from a backup."));
+            }
+            MessageType::AutosaveFailed(error) => {
+                message.push(MessagePart::colour(colours::RED, "Autosave failed. "));
+                self.translate_save_file_error(error, message);
+            }
         }
 
         if repeated > 1 {
@@ -1,4 +1,5 @@
 use game::*;
+use game::data::{ProjectileType, StatusEffectType};
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum MessageType {
@@ -16,6 +17,23 @@ pub enum MessageType {
     YouSeeDescription(YouSeeMessageType),
     NoDescription,
     Menu(MenuMessageType),
+    SaveLoadFailed(save_file::SaveFileError),
+    /// The current save couldn't be read, so the game was restored from a backup
+    RestoredFromBackup(save_file::SaveFileError),
+    AutosaveFailed(save_file::SaveFileError),
+    ChooseSaveSlot,
+    HighScores,
+    NoRuns,
+    /// The run history couldn't be parsed, so it was moved aside
+    RunHistoryCorrupt,
+    RunHistoryUnreadable,
+    DailyLeaderboard(u64),
+    DailyAlreadyAttempted,
+    CharacterStats,
+    RuleTrace,
+    RuleTraceEmpty,
+    /// The projectile which will be fired when aiming
+    SelectedProjectile(ProjectileType),
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -28,6 +46,25 @@ pub enum YouSeeMessageType {
 pub enum ActionMessageType {
     PlayerOpenDoor,
     PlayerCloseDoor,
+    PlayerReload,
+    PlayerMagazineFull,
+    PlayerOutOfAmmo,
+    /// The player tried to fire with an empty magazine
+    PlayerClickEmpty,
+    Explosion,
+    /// The tiles of the attacker and the victim
+    Hit(TileType, TileType),
+    CriticalHit(TileType, TileType),
+    Miss(TileType, TileType),
+    /// A hit which the victim is immune to
+    NoEffect(TileType, TileType),
+    /// The tile of the affected entity
+    StatusEffectStart(TileType, StatusEffectType),
+    StatusEffectEnd(TileType, StatusEffectType),
+    /// The tile of an entity which is too frightened to attack
+    TooFrightenedToAttack(TileType),
+    /// The tile of a door, tree or wall which has been destroyed
+    Destroyed(TileType),
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
@@ -38,11 +75,15 @@ pub enum DescriptionMessageType {
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum MenuMessageType {
     NewGame,
+    DailyChallenge,
+    LoadGame,
     Continue,
     Quit,
     SaveAndQuit,
     Controls,
+    HighScores,
     Control(InputEvent, Control),
     UnboundControl(Control),
     ControlBinding(Control),
+    SaveSlot(usize, save_slot::SaveSlotSummary),
 }
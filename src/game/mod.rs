@@ -26,6 +26,18 @@ mod menu;
 mod config;
 mod renderer_buffers;
 mod control_spec;
+mod replay;
+mod state_trace;
+mod clock;
+mod undo_journal;
+mod autosave;
+mod timestamp;
+mod run_history;
+mod daily;
+mod rule_trace;
+mod event_bus;
+#[cfg(test)]
+mod test_env;
 
 pub use self::knowledge::*;
 pub use self::behaviour::*;
@@ -54,11 +66,29 @@ pub use self::menu::*;
 pub use self::config::*;
 pub use self::renderer_buffers::*;
 pub use self::control_spec::*;
+pub use self::replay::*;
+pub use self::state_trace::*;
+pub use self::clock::*;
+pub use self::undo_journal::*;
+pub use self::autosave::*;
+pub use self::timestamp::*;
+pub use self::run_history::*;
+pub use self::daily::*;
+pub use self::rule_trace::*;
+pub use self::event_bus::*;
+#[cfg(test)]
+pub use self::test_env::*;
 
 pub mod data;
 pub mod prototypes;
 pub mod frontends;
 pub mod save_file;
+pub mod save_slot;
 pub mod game_file;
 pub mod user_files;
 pub mod control_file;
+pub mod replay_file;
+pub mod state_trace_file;
+pub mod morgue_file;
+pub mod run_history_file;
+pub mod daily_file;
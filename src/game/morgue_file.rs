@@ -0,0 +1,120 @@
+use std::path::{Path, PathBuf};
+
+use game::*;
+use game::game_file::FileResult;
+use coord::Coord;
+
+fn tile_char(tile: TileType) -> char {
+    match tile {
+        TileType::Wall | TileType::CrackedWall => '#',
+        TileType::Rubble => ':',
+        TileType::Tree => '&',
+        TileType::DeadTree => '%',
+        TileType::TreeStump => '_',
+        TileType::Floor | TileType::Ground => '.',
+        TileType::OpenDoor => '-',
+        TileType::ClosedDoor => '+',
+        TileType::BrokenDoor => '\'',
+        TileType::Bullet => '*',
+        TileType::Pellet => ',',
+        TileType::PiercingRound => '~',
+        TileType::ExplosiveRound => 'o',
+        TileType::SilverBullet => '*',
+        TileType::Player => '@',
+        TileType::TerrorPillar => 't',
+        TileType::TerrorFly => 'T',
+        TileType::Hunter => 'h',
+        TileType::Book => '=',
+        TileType::UpStairs => '<',
+        TileType::DownStairs => '>',
+    }
+}
+
+fn message_to_string(message: &Message) -> String {
+    message.iter().map(|part| {
+        match *part {
+            MessagePart::Text(TextMessagePart::Plain(ref s)) |
+                MessagePart::Text(TextMessagePart::Colour(_, ref s)) => s.as_str(),
+            MessagePart::Newline => "\n",
+        }
+    }).collect()
+}
+
+/// Draws the player's memory of a level, leaving cells which were never
+/// seen blank
+fn map_to_string(knowledge: &DrawableKnowledgeLevel) -> String {
+    let mut string = String::new();
+
+    for y in 0..knowledge.height() {
+        let mut row = String::new();
+        for x in 0..knowledge.width() {
+            let cell = knowledge.get_with_default(Coord::new(x as isize, y as isize));
+            let ch = if cell.last_updated() == 0 {
+                ' '
+            } else {
+                cell.foreground().or(cell.background()).map_or(' ', tile_char)
+            };
+            row.push(ch);
+        }
+        string.push_str(row.trim_right());
+        string.push('\n');
+    }
+
+    string
+}
+
+fn contents(date: UtcDateTime,
+            game_state: &GameState,
+            num_messages: usize,
+            language: &Box<Language>) -> String {
+
+    let stats = game_state.stats();
+    let cause = stats.killed_by.map_or_else(|| "unknown".to_string(), |cause| format!("{:?}", cause));
+
+    let mut string = String::new();
+
+    string.push_str("HOWL morgue file\n\n");
+    string.push_str(&format!("Date: {}\n", date));
+    string.push_str(&format!("Seed: {}\n", game_state.seed()));
+    string.push_str(&format!("Turns: {}\n", stats.pc_turns));
+    string.push_str(&format!("Levels visited: {}\n", game_state.num_levels()));
+    string.push_str(&format!("Enemies killed: {}\n", stats.enemies_killed));
+    string.push_str(&format!("Cause of death: {}\n", cause));
+
+    if let Some(pc) = game_state.pc_entity() {
+        if let Some(message_log) = pc.message_log_borrow() {
+            string.push_str("\nLast messages:\n");
+            let mut message = Message::new();
+            for entry in message_log.tail(num_messages) {
+                message.clear();
+                language.translate_repeated(entry.message, entry.repeated, &mut message);
+                string.push_str(&format!("  {}\n", message_to_string(&message)));
+            }
+        }
+
+        if let (Some(knowledge), Some(level_id)) = (pc.drawable_knowledge_borrow(), game_state.level_id()) {
+            string.push_str(&format!("\nFinal map (level {}):\n\n", level_id + 1));
+            string.push_str(&map_to_string(knowledge.level(level_id)));
+        }
+    }
+
+    string
+}
+
+/// Writes a description of a finished game to a new file in the user
+/// directory, returning the path of the file. Games which finish within the
+/// same second are written to separate files.
+pub fn write<P: AsRef<Path>>(user_path: P,
+                             game_state: &GameState,
+                             num_messages: usize,
+                             language: &Box<Language>) -> FileResult<PathBuf> {
+
+    let date = UtcDateTime::now();
+    let stem = format!("morgue-{:04}{:02}{:02}-{:02}{:02}{:02}",
+                       date.year, date.month, date.day, date.hour, date.minute, date.second);
+    let path = user_files::unique_path(user_path, &stem, "txt");
+
+    game_file::write_string(&path, contents(date, game_state, num_messages, language))?;
+
+    Ok(path)
+}
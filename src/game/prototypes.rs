@@ -22,6 +22,22 @@ pub fn wall<E: EntityPopulate>(mut entity: E, position: Coord) -> E {
     entity
 }
 
+/// A wall which can be shot or smashed through
+pub fn cracked_wall<E: EntityPopulate>(mut entity: E, position: Coord) -> E {
+    entity.insert_position(position);
+    entity.insert_opacity(1.0);
+    entity.insert_solid();
+
+    entity.insert_tile(TileType::CrackedWall);
+
+    entity.insert_tile_depth(1);
+    entity.insert_hit_points(HitPoints::new(3));
+    entity.insert_bump_attackable();
+    entity.insert_destructible(TileType::Rubble);
+
+    entity
+}
+
 pub fn tree(action: &mut EcsAction, ids: &EntityIdReserver, position: Coord) -> EntityId {
 
     let shadow_id = {
@@ -47,6 +63,9 @@ pub fn tree(action: &mut EcsAction, ids: &EntityIdReserver, position: Coord) ->
     entity.insert_shadow_entity(shadow_id);
     entity.insert_transformation_state(TransformationState::Real);
     entity.insert_transformation_type(TransformationType::Tree);
+    entity.insert_hit_points(HitPoints::new(4));
+    entity.insert_bump_attackable();
+    entity.insert_destructible(TileType::TreeStump);
 
     entity.id()
 }
@@ -95,6 +114,11 @@ pub fn pc<E: EntityPopulate>(mut entity: E, position: Coord) -> E {
     entity.insert_projectile_collider();
     entity.insert_hit_points(HitPoints::new(10));
     entity.insert_bump_attackable();
+    entity.insert_bump_attacker(MeleeAttack::new(Damage::physical(1), 0.8, 0.1, 2));
+    entity.insert_evasion(0.1);
+    entity.insert_magazine(Magazine::new(6, 12));
+    entity.insert_projectile_type(ProjectileType::Bullet);
+    entity.insert_character_stats(CharacterStats::new());
 
     entity
 }
@@ -109,6 +133,13 @@ pub fn terror_pillar(action: &mut EcsAction, ids: &EntityIdReserver, position: C
         entity.insert_turn_time(TURN_DURATION_BASE / 2);
         entity.insert_transformation_state(TransformationState::Other);
 
+        // only silver can hurt it in its true form
+        entity.insert_armour(0);
+        entity.insert_resistances(Resistances::new()
+                                  .with(DamageType::Physical, Resistance::Immune)
+                                  .with(DamageType::Silver, Resistance::Vulnerable)
+                                  .with(DamageType::Otherworldly, Resistance::Immune));
+
         entity.id()
     };
 
@@ -132,11 +163,38 @@ pub fn terror_pillar(action: &mut EcsAction, ids: &EntityIdReserver, position: C
     entity.insert_enemy();
     entity.insert_projectile_collider();
     entity.insert_hit_points(HitPoints::new(2));
-    entity.insert_bump_attacker(1);
+    // enough to take the edge off bullets and critical hits
+    entity.insert_armour(1);
+    entity.insert_resistances(Resistances::new().with(DamageType::Otherworldly, Resistance::Resistant));
+    entity.insert_bump_attacker(MeleeAttack::new(Damage::new(1, DamageType::Otherworldly), 0.7, 0.05, 2));
+    entity.insert_inflicts_status_effect(StatusEffect::new(StatusEffectType::Terrified, 3));
+    entity.insert_evasion(0.1);
+    entity.insert_bump_attackable();
 
     entity.id()
 }
 
+pub fn hunter<E: EntityPopulate>(mut entity: E, position: Coord) -> E {
+    entity.insert_position(position);
+
+    entity.insert_tile(TileType::Hunter);
+
+    entity.insert_tile_depth(2);
+    entity.insert_collider();
+    entity.insert_behaviour_state(BehaviourState::new());
+    entity.insert_behaviour_type(BehaviourType::RangedNpc);
+    entity.insert_turn_offset(NPC_TURN_OFFSET);
+    entity.insert_vision_distance(8);
+    entity.insert_simple_npc_knowledge(SimpleNpcKnowledge::new());
+    entity.insert_turn_time(TURN_DURATION_BASE * 2);
+    entity.insert_enemy();
+    entity.insert_projectile_collider();
+    entity.insert_hit_points(HitPoints::new(2));
+    entity.insert_evasion(0.1);
+    entity.insert_bump_attackable();
+
+    entity
+}
 
 pub fn door<E: EntityPopulate>(mut entity: E, position: Coord, state: DoorState) -> E {
     entity.insert_position(position);
@@ -148,9 +206,12 @@ pub fn door<E: EntityPopulate>(mut entity: E, position: Coord, state: DoorState)
         entity.insert_tile(TileType::ClosedDoor);
         entity.insert_solid();
         entity.insert_opacity(1.0);
+        entity.insert_bump_attackable();
     }
     entity.insert_tile_depth(1);
     entity.insert_door_state(state);
+    entity.insert_hit_points(HitPoints::new(3));
+    entity.insert_destructible(TileType::BrokenDoor);
 
     entity
 }
@@ -161,7 +222,7 @@ pub fn bullet<E: EntityPopulate>(mut entity: E, position: Coord, velocity: Realt
     entity.insert_realtime_velocity(velocity);
     entity.insert_destroy_on_collision();
     entity.insert_projectile();
-    entity.insert_projectile_damage(1);
+    entity.insert_projectile_damage(Damage::physical(2));
 
     entity.insert_tile(TileType::Bullet);
 
@@ -170,6 +231,70 @@ pub fn bullet<E: EntityPopulate>(mut entity: E, position: Coord, velocity: Realt
     entity
 }
 
+pub fn pellet<E: EntityPopulate>(mut entity: E, position: Coord, velocity: RealtimeVelocity) -> E {
+
+    entity.insert_position(position);
+    entity.insert_realtime_velocity(velocity);
+    entity.insert_destroy_on_collision();
+    entity.insert_projectile();
+    entity.insert_projectile_damage(Damage::physical(1));
+
+    entity.insert_inflicts_status_effect(StatusEffect::new(StatusEffectType::Slowed, 2));
+
+    entity.insert_tile(TileType::Pellet);
+
+    entity.insert_tile_depth(1);
+
+    entity
+}
+
+pub fn piercing_round<E: EntityPopulate>(mut entity: E, position: Coord, velocity: RealtimeVelocity) -> E {
+
+    entity.insert_position(position);
+    entity.insert_realtime_velocity(velocity);
+    entity.insert_destroy_on_collision();
+    entity.insert_projectile();
+    entity.insert_projectile_damage(Damage::physical(2));
+    entity.insert_piercing();
+    entity.insert_inflicts_status_effect(StatusEffect::new(StatusEffectType::Bleeding, 3));
+
+    entity.insert_tile(TileType::PiercingRound);
+
+    entity.insert_tile_depth(1);
+
+    entity
+}
+
+pub fn explosive_round<E: EntityPopulate>(mut entity: E, position: Coord, velocity: RealtimeVelocity) -> E {
+
+    entity.insert_position(position);
+    entity.insert_realtime_velocity(velocity);
+    entity.insert_destroy_on_collision();
+    entity.insert_projectile();
+    entity.insert_explosive(Explosive::new(2, Damage::physical(2)));
+
+    entity.insert_tile(TileType::ExplosiveRound);
+
+    entity.insert_tile_depth(1);
+
+    entity
+}
+
+pub fn silver_bullet<E: EntityPopulate>(mut entity: E, position: Coord, velocity: RealtimeVelocity) -> E {
+
+    entity.insert_position(position);
+    entity.insert_realtime_velocity(velocity);
+    entity.insert_destroy_on_collision();
+    entity.insert_projectile();
+    entity.insert_projectile_damage(Damage::new(1, DamageType::Silver));
+
+    entity.insert_tile(TileType::SilverBullet);
+
+    entity.insert_tile_depth(1);
+
+    entity
+}
+
 pub fn tear<E: EntityPopulate, R: Rng>(mut entity: E, width: usize, height: usize, r: &mut R) -> E {
 
     const PERLIN_X_ZOOM: f64 = 0.05;
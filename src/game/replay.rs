@@ -0,0 +1,111 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+use std::time::Duration;
+
+use game::*;
+use game::replay_file::ReplayWriter;
+
+/// Everything needed to reproduce the start of a session
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    pub seed: usize,
+    /// Debug mode changes the available controls and enables rewinding
+    pub debug: bool,
+    pub slots: Vec<ReplaySlot>,
+    pub controls: Option<String>,
+    pub config: Option<String>,
+    pub daily_leaderboard: Option<String>,
+    /// Day of the daily challenge which was current when the session started
+    pub daily_challenge_day: u64,
+}
+
+/// Contents of an occupied save slot at the start of a session
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReplaySlot {
+    pub slot: usize,
+    pub save: Vec<u8>,
+    pub info: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    pub event: InputEvent,
+    /// Time in milliseconds since the previous event
+    pub delay_ms: u64,
+}
+
+#[derive(Debug)]
+pub struct Replay {
+    pub header: ReplayHeader,
+    pub events: Vec<ReplayEvent>,
+}
+
+/// Input source which writes every event it yields to a replay file
+#[derive(Clone)]
+pub struct RecordingInputSource<I: InputSource> {
+    input_source: I,
+    writer: Option<Rc<RefCell<ReplayWriter>>>,
+}
+
+impl<I: InputSource> RecordingInputSource<I> {
+    pub fn new(input_source: I, writer: Option<ReplayWriter>) -> Self {
+        RecordingInputSource {
+            input_source: input_source,
+            writer: writer.map(|w| Rc::new(RefCell::new(w))),
+        }
+    }
+}
+
+impl<I: InputSource> InputSource for RecordingInputSource<I> {
+    fn next_input(&mut self) -> Option<InputEvent> {
+        let event = self.input_source.next_input();
+
+        if let Some(event) = event {
+            if let Some(ref writer) = self.writer {
+                // failing to record shouldn't interrupt the game
+                let _ = writer.borrow_mut().write_event(event);
+            }
+        }
+
+        event
+    }
+}
+
+/// Input source which yields the events of a replay, and then falls back to
+/// another input source once the replay is exhausted
+#[derive(Clone)]
+pub struct ReplayInputSource<I: InputSource> {
+    events: Rc<RefCell<VecDeque<ReplayEvent>>>,
+    clock: Rc<Box<Clock>>,
+    input_source: I,
+}
+
+impl<I: InputSource> ReplayInputSource<I> {
+    /// The delays between events are waited for with `clock`, so a scaled
+    /// or instant clock speeds up the replay
+    pub fn new(events: Vec<ReplayEvent>, clock: Box<Clock>, input_source: I) -> Self {
+        ReplayInputSource {
+            events: Rc::new(RefCell::new(events.into_iter().collect())),
+            clock: Rc::new(clock),
+            input_source: input_source,
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.events.borrow().len()
+    }
+}
+
+impl<I: InputSource> InputSource for ReplayInputSource<I> {
+    fn next_input(&mut self) -> Option<InputEvent> {
+        let next = self.events.borrow_mut().pop_front();
+
+        if let Some(ReplayEvent { event, delay_ms }) = next {
+            self.clock.sleep(Duration::from_millis(delay_ms));
+            Some(event)
+        } else {
+            self.input_source.next_input()
+        }
+    }
+}
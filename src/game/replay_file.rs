@@ -0,0 +1,147 @@
+use std::path::Path;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::time::Instant;
+use bincode::{self, SizeLimit};
+use game::*;
+use game::game_file::{FileError, FileResult};
+
+/// A replay file consists of a header followed by a sequence of events.
+/// Events are written as they occur, so a session which ends in a crash
+/// still leaves a usable replay.
+pub struct ReplayWriter {
+    file: File,
+    last_event: Instant,
+}
+
+impl ReplayWriter {
+    /// Creates a new replay file in the replays directory of the user
+    /// directory, named after the time the session started
+    pub fn create<P: AsRef<Path>>(user_path: P, header: &ReplayHeader) -> FileResult<Self> {
+        let replays_path = user_path.as_ref().join(user_files::REPLAYS);
+        fs::create_dir_all(&replays_path).map_err(|_| FileError::FailedToWrite)?;
+
+        let date = UtcDateTime::now();
+        let stem = format!("replay-{:04}{:02}{:02}-{:02}{:02}{:02}",
+                           date.year, date.month, date.day, date.hour, date.minute, date.second);
+
+        let mut file = File::create(user_files::unique_path(replays_path, &stem, "replay"))
+            .map_err(|_| FileError::FailedToWrite)?;
+
+        let encoded = bincode::serialize(header, SizeLimit::Infinite).map_err(|_| FileError::InvalidFormat)?;
+        file.write_all(&encoded).map_err(|_| FileError::FailedToWrite)?;
+
+        Ok(ReplayWriter {
+            file: file,
+            last_event: Instant::now(),
+        })
+    }
+
+    pub fn write_event(&mut self, event: InputEvent) -> FileResult<()> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_event);
+        self.last_event = now;
+
+        let replay_event = ReplayEvent {
+            event: event,
+            delay_ms: elapsed.as_secs() * 1000 + (elapsed.subsec_nanos() / 1_000_000) as u64,
+        };
+
+        let encoded = bincode::serialize(&replay_event, SizeLimit::Infinite).map_err(|_| FileError::InvalidFormat)?;
+        self.file.write_all(&encoded).map_err(|_| FileError::FailedToWrite)?;
+        self.file.flush().map_err(|_| FileError::FailedToWrite)
+    }
+}
+
+fn read_bytes<P: AsRef<Path>>(path: P) -> Option<Vec<u8>> {
+    File::open(path).ok().and_then(|mut f| {
+        let mut bytes = Vec::new();
+        f.read_to_end(&mut bytes).ok().map(|_| bytes)
+    })
+}
+
+/// Captures the settings and user files which affect how a session plays out
+pub fn header<P: AsRef<Path>, Q: AsRef<Path>>(user_path: P, shared_user_path: Q, seed: usize, debug: bool) -> ReplayHeader {
+    let slots = save_slot::slots().filter_map(|slot| {
+        let slot_path = save_slot::slot_path(user_path.as_ref(), slot);
+        read_bytes(slot_path.join(user_files::SAVE)).map(|save| {
+            ReplaySlot {
+                slot: slot,
+                save: save,
+                info: game_file::read_string(slot_path.join(user_files::SLOT_INFO)).ok(),
+            }
+        })
+    }).collect();
+
+    ReplayHeader {
+        seed: seed,
+        debug: debug,
+        slots: slots,
+        controls: game_file::read_string(user_path.as_ref().join(user_files::CONTROL)).ok(),
+        config: game_file::read_string(user_path.as_ref().join(user_files::CONFIG)).ok(),
+        daily_leaderboard: game_file::read_string(shared_user_path.as_ref().join(user_files::DAILY)).ok(),
+        daily_challenge_day: daily_challenge_day(),
+    }
+}
+
+pub fn load<P: AsRef<Path>>(path: P) -> FileResult<Replay> {
+    let mut file = File::open(path).map_err(|_| FileError::MissingFile)?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).map_err(|_| FileError::InvalidFile)?;
+
+    let mut reader = bytes.as_slice();
+    let header: ReplayHeader = bincode::deserialize_from(&mut reader, SizeLimit::Infinite)
+        .map_err(|_| FileError::InvalidFormat)?;
+
+    let mut events = Vec::new();
+    while !reader.is_empty() {
+        match bincode::deserialize_from(&mut reader, SizeLimit::Infinite) {
+            Ok(event) => events.push(event),
+            // the last event may have been partially written
+            Err(_) => break,
+        }
+    }
+
+    Ok(Replay {
+        header: header,
+        events: events,
+    })
+}
+
+/// Populates a fresh user directory with the files captured in a replay's
+/// header. The directory is used for both the profile and shared files.
+pub fn prepare_session<P: AsRef<Path>>(header: &ReplayHeader, session_path: P) -> FileResult<()> {
+    let session_path = session_path.as_ref();
+
+    if session_path.is_dir() {
+        fs::remove_dir_all(session_path).map_err(|_| FileError::FailedToWrite)?;
+    }
+    fs::create_dir_all(session_path).map_err(|_| FileError::FailedToWrite)?;
+
+    for replay_slot in header.slots.iter() {
+        let slot_path = save_slot::slot_path(session_path, replay_slot.slot);
+        fs::create_dir_all(&slot_path).map_err(|_| FileError::FailedToWrite)?;
+
+        File::create(slot_path.join(user_files::SAVE))
+            .and_then(|mut f| f.write_all(&replay_slot.save))
+            .map_err(|_| FileError::FailedToWrite)?;
+
+        if let Some(ref info) = replay_slot.info {
+            game_file::write_string(slot_path.join(user_files::SLOT_INFO), info)?;
+        }
+    }
+
+    if let Some(ref controls) = header.controls {
+        game_file::write_string(session_path.join(user_files::CONTROL), controls)?;
+    }
+
+    if let Some(ref config) = header.config {
+        game_file::write_string(session_path.join(user_files::CONFIG), config)?;
+    }
+
+    if let Some(ref daily_leaderboard) = header.daily_leaderboard {
+        game_file::write_string(session_path.join(user_files::DAILY), daily_leaderboard)?;
+    }
+
+    Ok(())
+}
@@ -12,6 +12,11 @@ impl GameRng {
         }
     }
 
+    /// Restarts the sequence of random numbers as if newly created with a seed
+    pub fn reseed(&self, seed: usize) {
+        *self.rng.borrow_mut() = StdRng::from_seed(&[seed]);
+    }
+
     pub fn gen<T: Rand>(&self) -> T {
         self.rng.borrow_mut().gen()
     }
@@ -38,6 +43,14 @@ impl GameRng {
         return max;
     }
 
+    pub fn state(&self) -> StdRng {
+        self.rng.borrow().clone()
+    }
+
+    pub fn set_state(&self, state: StdRng) {
+        *self.rng.borrow_mut() = state;
+    }
+
     pub fn inner_mut(&self) -> RefMut<StdRng> {
         self.rng.borrow_mut()
     }
@@ -1,8 +1,14 @@
 use game::*;
+use game::data::*;
 use ecs::*;
 
 pub fn bump_attack(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
 
+    if action.knockback().is_some() {
+        // being knocked into something isn't an attack
+        return RULE_ACCEPT;
+    }
+
     for (attacker_id, position) in action.position_profile().insertion_copy_iter() {
 
         if let Some(victim_id) = env.spatial_hash.get(position).any_bump_attackable() {
@@ -10,9 +16,21 @@ pub fn bump_attack(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reactio
             let victim = env.ecs.entity(victim_id);
             let attacker = env.ecs.entity(attacker_id);
 
-            if let Some(damage) = attacker.bump_attacker() {
+            if attacker.contains_enemy() && victim.contains_enemy() {
+                // enemies don't attack each other
+                continue;
+            }
+
+            if attacker.contains_bump_attacker() {
+                if attacker.status_effects().map_or(false, |s| s.is_active(StatusEffectType::Terrified)) {
+                    // only attacking a character takes nerve; terrain just blocks the move
+                    if victim.contains_enemy() || victim.contains_pc() {
+                        reactions.push(Reaction::new(ActionArgs::Cower(attacker_id), 0));
+                    }
+                    return RULE_REJECT;
+                }
                 if victim.contains_hit_points() {
-                    reactions.push(Reaction::new(ActionArgs::Damage(victim_id, damage), 0));
+                    reactions.push(Reaction::new(ActionArgs::MeleeAttack(attacker_id, victim_id), 0));
                 }
                 return RULE_REJECT;
             }
@@ -1,7 +1,7 @@
 use game::*;
 use ecs::*;
 
-pub fn close_door(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+pub fn close_door(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
 
     if let Some(close) = action.close() {
         let entity = env.ecs.entity(close.entity_id);
@@ -12,6 +12,4 @@ pub fn close_door(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction
             reactions.push(Reaction::new(ActionArgs::CloseDoor(door_id), 0));
         }
     }
-
-    RULE_ACCEPT
 }
@@ -1,11 +1,32 @@
 use game::*;
+use game::data::*;
 use ecs::*;
 
+/// Hurts and stuns an entity which was knocked into something
+fn knockback_impact(entity_id: EntityId, reactions: &mut Vec<Reaction>) {
+    reactions.push(Reaction::new(ActionArgs::Damage(entity_id, KNOCKBACK_IMPACT_DAMAGE, DamageSource::Impact), 0));
+    let stun = StatusEffect::new(StatusEffectType::Stunned, KNOCKBACK_IMPACT_STUN_DURATION);
+    reactions.push(Reaction::new(ActionArgs::ApplyStatusEffect(entity_id, stun), 0));
+}
+
 pub fn collision(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
 
     for (entity_id, position) in action.position_profile().insertion_copy_iter() {
 
-        if !env.spatial_hash.get(position).solid() {
+        let cell = env.spatial_hash.get(position);
+        let knocked_back = action.knockback().map_or(false, |knockback| knockback.entity_id == entity_id);
+
+        if knocked_back && !cell.solid() && (cell.enemy() || cell.pc()) {
+            // knocked into another character, which is hurt by the impact too
+            knockback_impact(entity_id, reactions);
+            if let Some(other_id) = cell.any_bump_attackable() {
+                let damage_action = ActionArgs::Damage(other_id, KNOCKBACK_IMPACT_DAMAGE, DamageSource::Entity(entity_id));
+                reactions.push(Reaction::new(damage_action, 0));
+            }
+            return RULE_REJECT;
+        }
+
+        if !cell.solid() {
             continue;
         }
 
@@ -13,9 +34,20 @@ pub fn collision(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>
 
         let mut reject = false;
         if entity.contains_collider() {
+            if knocked_back {
+                // knocked into something solid
+                knockback_impact(entity_id, reactions);
+            }
             reject = true;
         }
         if entity.contains_destroy_on_collision() {
+            if entity.contains_projectile() {
+                if let Some(terrain_id) = cell.any_destructible() {
+                    // projectiles damage the doors, trees and walls they hit
+                    let collision_action = ActionArgs::ProjectileCollision(ProjectileCollision::new(entity_id, terrain_id));
+                    reactions.push(Reaction::new(collision_action, 0));
+                }
+            }
             reactions.push(Reaction::new(ActionArgs::Destroy(entity_id), 0));
             reject = true;
         }
@@ -1,13 +1,15 @@
 use game::*;
 use ecs::*;
 
-pub fn death(_env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+pub fn death(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
 
     for (entity_id, hit_points) in action.hit_points_profile().insertion_copy_iter() {
         if !hit_points.is_positive() {
-            reactions.push(Reaction::new(ActionArgs::Die(entity_id), 0));
+            if env.ecs.contains_destructible(entity_id) {
+                reactions.push(Reaction::new(ActionArgs::DestroyTerrain(entity_id), 0));
+            } else {
+                reactions.push(Reaction::new(ActionArgs::Die(entity_id), 0));
+            }
         }
     }
-
-    RULE_ACCEPT
 }
@@ -0,0 +1,14 @@
+use game::*;
+use ecs::*;
+
+pub fn explosion(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
+
+    // explosives go off when they are destroyed
+    for entity_id in action.explosive_negative_iter(env.ecs) {
+        let entity = env.ecs.entity(entity_id);
+        if let Some(position) = entity.position() {
+            let explosive = entity.explosive().expect("Entity missing explosive");
+            reactions.push(Reaction::new(ActionArgs::Explode(position, explosive), 0));
+        }
+    }
+}
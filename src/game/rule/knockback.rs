@@ -0,0 +1,11 @@
+use game::*;
+use ecs::*;
+
+pub fn knockback(_env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
+
+    if let Some(knockback) = action.knockback() {
+        if knockback.distance > 0 {
+            reactions.push(Reaction::new(ActionArgs::Knockback(knockback), 0));
+        }
+    }
+}
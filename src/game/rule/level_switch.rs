@@ -1,7 +1,7 @@
 use game::*;
 use ecs::*;
 
-pub fn level_switch(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+pub fn level_switch(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
 
     if let Some(entity_id) = action.try_level_switch() {
         // the character tried to switch levels
@@ -24,11 +24,9 @@ pub fn level_switch(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reacti
             }
         }
     }
-
-    RULE_ACCEPT
 }
 
-pub fn level_switch_auto(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+pub fn level_switch_auto(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
 
     for (entity_id, position) in action.position_profile().insertion_copy_iter() {
 
@@ -53,6 +51,4 @@ pub fn level_switch_auto(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<R
             }
         }
     }
-
-    RULE_ACCEPT
 }
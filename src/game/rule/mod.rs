@@ -1,6 +1,9 @@
 mod types;
 pub use self::types::*;
 
+mod registry;
+pub use self::registry::*;
+
 mod collision;
 mod open_door;
 mod close_door;
@@ -11,6 +14,9 @@ mod projectile_collision;
 mod death;
 mod enemy_collision;
 mod bump_attack;
+mod explosion;
+mod knockback;
+mod status_effect;
 
 pub mod rules {
     pub use super::collision::*;
@@ -23,4 +29,10 @@ pub mod rules {
     pub use super::death::*;
     pub use super::enemy_collision::*;
     pub use super::bump_attack::*;
+    pub use super::explosion::*;
+    pub use super::knockback::*;
+    pub use super::status_effect::*;
 }
+
+#[cfg(test)]
+mod tests;
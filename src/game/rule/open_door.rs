@@ -3,6 +3,11 @@ use ecs::*;
 
 pub fn open_door(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
 
+    if action.knockback().is_some() {
+        // entities knocked into doors collide with them instead
+        return RULE_ACCEPT;
+    }
+
     for (entity_id, position) in action.position_profile().insertion_copy_iter() {
 
         if let Some(door_id) = env.spatial_hash.get(position).any_door() {
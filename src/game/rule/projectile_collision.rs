@@ -12,13 +12,19 @@ pub fn projectile_collision_trigger(env: RuleEnv, action: &EcsAction, reactions:
 
             if projectile.contains_projectile() {
 
+                let collision_action = ActionArgs::ProjectileCollision(ProjectileCollision::new(projectile_id, collider_id));
+
+                if projectile.contains_piercing() {
+                    // piercing projectiles continue moving after the collision
+                    reactions.push(Reaction::new(collision_action, 0));
+                    continue;
+                }
+
                 if projectile.contains_destroy_on_collision() {
                     // must happen after processing the collision
                     reactions.push(Reaction::new(ActionArgs::Destroy(projectile_id), 1));
                 }
 
-                let collision_action = ActionArgs::ProjectileCollision(ProjectileCollision::new(projectile_id, collider_id));
-
                 return rule_consume(collision_action);
             }
         }
@@ -35,7 +41,14 @@ pub fn projectile_collision(env: RuleEnv, action: &EcsAction, reactions: &mut Ve
 
         if let Some(damage) = projectile.projectile_damage() {
             if env.ecs.contains_hit_points(collider_id) {
-                reactions.push(Reaction::new(ActionArgs::Damage(collider_id, damage), 0));
+                let damage_action = ActionArgs::Damage(collider_id, damage, DamageSource::Entity(projectile_id));
+                reactions.push(Reaction::new(damage_action, 0));
+            }
+        }
+
+        if let Some(effect) = projectile.inflicts_status_effect() {
+            if env.ecs.contains_hit_points(collider_id) {
+                reactions.push(Reaction::new(ActionArgs::ApplyStatusEffect(collider_id, effect), 0));
             }
         }
     }
@@ -1,7 +1,7 @@
 use game::*;
 use ecs::*;
 
-pub fn realtime_velocity_start(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+pub fn realtime_velocity_start(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
 
     for (entity_id, velocity) in action.realtime_velocity_positive_iter(env.ecs) {
         if env.ecs.realtime_velocity(entity_id).is_none() {
@@ -9,11 +9,9 @@ pub fn realtime_velocity_start(env: RuleEnv, action: &EcsAction, reactions: &mut
             reactions.push(Reaction::new(ActionArgs::RealtimeVelocityMove(entity_id, *velocity), delay));
         }
     }
-
-    RULE_ACCEPT
 }
 
-pub fn realtime_velocity(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+pub fn realtime_velocity(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
 
     for (entity_id, _position) in action.position_profile().insertion_copy_iter() {
         let entity = env.ecs.entity(entity_id);
@@ -21,6 +19,4 @@ pub fn realtime_velocity(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<R
             reactions.push(Reaction::new(ActionArgs::RealtimeVelocityMove(entity_id, *velocity), 0));
         }
     }
-
-    RULE_ACCEPT
 }
@@ -0,0 +1,200 @@
+use ecs::*;
+use game::*;
+
+pub type RuleFn = fn(RuleEnv, &EcsAction, &mut Vec<Reaction>) -> RuleResult;
+
+/// A rule which only schedules further actions, so has no way to reject
+pub type ReactionFn = fn(RuleEnv, &EcsAction, &mut Vec<Reaction>);
+
+/// Names every rule which can be registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleId {
+    OpenDoor,
+    BumpAttack,
+    Collision,
+    ProjectileCollisionTrigger,
+    EnemyCollision,
+    PcCollision,
+    CloseDoor,
+    TearTransform,
+    RealtimeVelocityStart,
+    RealtimeVelocity,
+    Death,
+    Explosion,
+    Knockback,
+    StatusEffectInfliction,
+    StatusEffectEnd,
+    LevelSwitch,
+    LevelSwitchAuto,
+    TearMoveTransform,
+    ProjectileCollision,
+}
+
+/// Determines which actions a rule is checked against, and what it may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RulePhase {
+    /// Checked against actions which will be committed if accepted. These
+    /// rules may reject the action or replace it with another.
+    PreCommit,
+    /// Checked against actions which will be committed if accepted. These
+    /// rules schedule further actions in response, and can't reject.
+    Reaction,
+    /// Checked against actions which are never committed, and exist only to
+    /// trigger rules
+    NoCommit,
+}
+
+#[derive(Clone, Copy)]
+enum RuleFunction {
+    Rule(RuleFn),
+    Reaction(ReactionFn),
+}
+
+struct RuleEntry {
+    id: RuleId,
+    phase: RulePhase,
+    function: RuleFunction,
+    enabled: bool,
+}
+
+/// The rules checked against each action. Rules are checked in the order
+/// they were registered, and checking stops at the first rule which doesn't
+/// accept the action. Pre-commit and reaction rules are checked against the
+/// same actions, so a reaction rule registered before a pre-commit rule
+/// schedules its actions even if the pre-commit rule rejects the action.
+pub struct RuleRegistry {
+    entries: Vec<RuleEntry>,
+}
+
+impl RuleRegistry {
+    pub fn new() -> Self {
+        RuleRegistry {
+            entries: Vec::new(),
+        }
+    }
+
+    /// The rules of the game, in the order they must be checked
+    pub fn standard() -> Self {
+        let mut registry = Self::new();
+
+        // open doors and attack rather than walking into doors and enemies
+        registry.register(RuleId::OpenDoor, RulePhase::PreCommit, rules::open_door);
+        registry.register(RuleId::BumpAttack, RulePhase::PreCommit, rules::bump_attack);
+        registry.register(RuleId::Collision, RulePhase::PreCommit, rules::collision);
+        registry.register(RuleId::ProjectileCollisionTrigger, RulePhase::PreCommit, rules::projectile_collision_trigger);
+        registry.register_reaction(RuleId::CloseDoor, rules::close_door);
+        registry.register_reaction(RuleId::TearTransform, rules::tear_transform);
+        registry.register_reaction(RuleId::RealtimeVelocityStart, rules::realtime_velocity_start);
+        registry.register_reaction(RuleId::RealtimeVelocity, rules::realtime_velocity);
+        registry.register_reaction(RuleId::Death, rules::death);
+        registry.register(RuleId::EnemyCollision, RulePhase::PreCommit, rules::enemy_collision);
+        registry.register(RuleId::PcCollision, RulePhase::PreCommit, rules::pc_collision);
+
+        // only react to actions no rule has rejected
+        registry.register_reaction(RuleId::Explosion, rules::explosion);
+        registry.register_reaction(RuleId::Knockback, rules::knockback);
+        registry.register_reaction(RuleId::StatusEffectInfliction, rules::status_effect_infliction);
+        registry.register_reaction(RuleId::StatusEffectEnd, rules::status_effect_end);
+        registry.register_reaction(RuleId::LevelSwitch, rules::level_switch);
+        registry.register_reaction(RuleId::LevelSwitchAuto, rules::level_switch_auto);
+        registry.register_reaction(RuleId::TearMoveTransform, rules::tear_move_transform);
+
+        registry.register(RuleId::ProjectileCollision, RulePhase::NoCommit, rules::projectile_collision);
+
+        registry
+    }
+
+    /// The rules used for games of a given mode. Rules disabled in the
+    /// configuration stay enabled in the daily challenge, so that everyone
+    /// plays it by the same rules.
+    pub fn for_mode(mode: GameMode, config: &RulesConfig) -> Self {
+        let mut registry = Self::standard();
+
+        if let GameMode::Normal = mode {
+            for name in config.disabled.iter() {
+                registry.set_enabled_by_name(name, false);
+            }
+        }
+
+        registry
+    }
+
+    /// Adds a pre-commit or no-commit rule to be checked after all
+    /// previously registered rules
+    pub fn register(&mut self, id: RuleId, phase: RulePhase, function: RuleFn) {
+        assert!(phase != RulePhase::Reaction, "Reaction rules must be registered with register_reaction");
+        self.entries.push(RuleEntry {
+            id: id,
+            phase: phase,
+            function: RuleFunction::Rule(function),
+            enabled: true,
+        });
+    }
+
+    /// Adds a reaction rule to be checked after all previously registered
+    /// rules
+    pub fn register_reaction(&mut self, id: RuleId, function: ReactionFn) {
+        self.entries.push(RuleEntry {
+            id: id,
+            phase: RulePhase::Reaction,
+            function: RuleFunction::Reaction(function),
+            enabled: true,
+        });
+    }
+
+    pub fn set_enabled(&mut self, id: RuleId, enabled: bool) {
+        for entry in self.entries.iter_mut() {
+            if entry.id == id {
+                entry.enabled = enabled;
+            }
+        }
+    }
+
+    /// Enables or disables a rule by the name shown in the rule trace.
+    /// Unknown names are ignored.
+    pub fn set_enabled_by_name(&mut self, name: &str, enabled: bool) {
+        for entry in self.entries.iter_mut() {
+            if format!("{:?}", entry.id) == name {
+                entry.enabled = enabled;
+            }
+        }
+    }
+
+    pub fn is_enabled(&self, id: RuleId) -> bool {
+        self.entries.iter().any(|entry| entry.id == id && entry.enabled)
+    }
+
+    /// Checks each enabled rule in any of the given phases against an
+    /// action, recording the outcome of each rule in the trace
+    pub fn check(&self,
+                 phases: &[RulePhase],
+                 env: RuleEnv,
+                 action: &EcsAction,
+                 reactions: &mut Vec<Reaction>,
+                 trace: &mut RuleTrace) -> RuleResult {
+
+        for entry in self.entries.iter() {
+            if entry.enabled && phases.contains(&entry.phase) {
+                let num_reactions = reactions.len();
+                let result = match entry.function {
+                    RuleFunction::Rule(function) => function(env, action, reactions),
+                    RuleFunction::Reaction(function) => {
+                        function(env, action, reactions);
+                        RULE_ACCEPT
+                    }
+                };
+
+                if trace.is_enabled() {
+                    let resolution = match result {
+                        Ok(resolution) | Err(resolution) => resolution,
+                    };
+                    trace.record_rule(entry.id, resolution, &reactions[num_reactions..]);
+                }
+
+                result?;
+            }
+        }
+
+        RULE_ACCEPT
+    }
+}
@@ -0,0 +1,23 @@
+use game::*;
+use game::data::*;
+use ecs::*;
+
+pub fn status_effect_infliction(_env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
+
+    if let Some(StatusEffectInfliction { entity_id, effect }) = action.status_effect_infliction() {
+        reactions.push(Reaction::new(ActionArgs::ApplyStatusEffect(entity_id, effect), 0));
+    }
+}
+
+pub fn status_effect_end(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
+
+    for (entity_id, status_effects) in action.status_effects_profile().insertion_copy_iter() {
+        if let Some(current) = env.ecs.status_effects(entity_id) {
+            for effect in current.iter() {
+                if !status_effects.is_active(effect.effect_type) {
+                    reactions.push(Reaction::new(ActionArgs::EndStatusEffect(entity_id, effect.effect_type), 0));
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use game::*;
 use ecs::*;
 
-pub fn tear_move_transform(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+pub fn tear_move_transform(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
 
     for (entity_id, new_position) in action.position_profile().insertion_copy_iter() {
 
@@ -22,11 +22,9 @@ pub fn tear_move_transform(env: RuleEnv, action: &EcsAction, reactions: &mut Vec
             }
         }
     }
-
-    RULE_ACCEPT
 }
 
-pub fn tear_transform(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+pub fn tear_transform(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reaction>) {
 
     for entity_id in action.tear_profile().insertion_iter() {
         if let Some(position) = env.ecs.position(entity_id) {
@@ -63,6 +61,4 @@ pub fn tear_transform(env: RuleEnv, action: &EcsAction, reactions: &mut Vec<Reac
             }
         }
     }
-
-    RULE_ACCEPT
 }
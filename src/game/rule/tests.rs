@@ -0,0 +1,333 @@
+use game::*;
+use game::data::*;
+use ecs::*;
+use direction::Direction;
+use coord::Coord;
+
+fn accept(_env: RuleEnv, _action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+    reactions.push(Reaction::new(ActionArgs::Null, 1));
+    RULE_ACCEPT
+}
+
+fn reject(_env: RuleEnv, _action: &EcsAction, reactions: &mut Vec<Reaction>) -> RuleResult {
+    reactions.push(Reaction::new(ActionArgs::Null, 2));
+    RULE_REJECT
+}
+
+fn react(_env: RuleEnv, _action: &EcsAction, reactions: &mut Vec<Reaction>) {
+    reactions.push(Reaction::new(ActionArgs::Null, 3));
+}
+
+fn react_later(_env: RuleEnv, _action: &EcsAction, reactions: &mut Vec<Reaction>) {
+    reactions.push(Reaction::new(ActionArgs::Null, 4));
+}
+
+/// Checks the registry against an empty action, returning whether it was
+/// accepted and the delays of the reactions, which identify the rules
+/// which were checked
+fn check(registry: &RuleRegistry, phases: &[RulePhase]) -> (bool, Vec<u64>) {
+    let env = env_with(EcsAction::new(), 1, 1);
+
+    let mut reactions = Vec::new();
+    let result = registry.check(phases, env.rule_env(), &EcsAction::new(), &mut reactions, &mut RuleTrace::disabled());
+
+    (result.is_ok(), reactions.iter().map(|reaction| reaction.delay).collect())
+}
+
+#[test]
+fn rules_are_checked_in_registration_order() {
+    let mut registry = RuleRegistry::new();
+    registry.register(RuleId::OpenDoor, RulePhase::PreCommit, accept);
+    registry.register_reaction(RuleId::CloseDoor, react);
+    registry.register(RuleId::Collision, RulePhase::PreCommit, reject);
+    registry.register_reaction(RuleId::Death, react_later);
+
+    // the reaction registered before the rejecting rule is still checked
+    assert_eq!(check(&registry, &[RulePhase::PreCommit, RulePhase::Reaction]), (false, vec![1, 3, 2]));
+}
+
+#[test]
+fn rules_are_only_checked_in_their_phases() {
+    let mut registry = RuleRegistry::new();
+    registry.register(RuleId::ProjectileCollision, RulePhase::NoCommit, accept);
+    registry.register_reaction(RuleId::Death, react);
+
+    assert_eq!(check(&registry, &[RulePhase::NoCommit]), (true, vec![1]));
+    assert_eq!(check(&registry, &[RulePhase::PreCommit, RulePhase::Reaction]), (true, vec![3]));
+}
+
+#[test]
+fn disabled_rules_are_not_checked() {
+    let mut registry = RuleRegistry::new();
+    registry.register(RuleId::Collision, RulePhase::PreCommit, reject);
+    registry.register_reaction(RuleId::Death, react);
+    registry.set_enabled(RuleId::Collision, false);
+
+    assert!(!registry.is_enabled(RuleId::Collision));
+    assert_eq!(check(&registry, &[RulePhase::PreCommit, RulePhase::Reaction]), (true, vec![3]));
+}
+
+#[test]
+#[should_panic]
+fn reaction_rules_need_a_reaction_function() {
+    RuleRegistry::new().register(RuleId::Death, RulePhase::Reaction, accept);
+}
+
+#[test]
+fn configured_rules_are_disabled_in_normal_games() {
+    let config = RulesConfig {
+        disabled: vec!["TearMoveTransform".to_string(), "NotARule".to_string()],
+    };
+
+    let registry = RuleRegistry::for_mode(GameMode::Normal, &config);
+    assert!(!registry.is_enabled(RuleId::TearMoveTransform));
+    assert!(registry.is_enabled(RuleId::Death));
+}
+
+#[test]
+fn configured_rules_stay_enabled_in_the_daily_challenge() {
+    let config = RulesConfig {
+        disabled: vec!["TearMoveTransform".to_string()],
+    };
+
+    let registry = RuleRegistry::for_mode(GameMode::Daily(0), &config);
+    assert!(registry.is_enabled(RuleId::TearMoveTransform));
+}
+
+const PROJECTILE_ID: EntityId = 0;
+const TARGET_ID: EntityId = 1;
+
+/// Checks the projectile collision trigger against a projectile moving onto
+/// a target, returning the result and the reactions
+fn projectile_hits_target(piercing: bool) -> (RuleResult, Vec<Reaction>) {
+    let mut action = EcsAction::new();
+
+    {
+        let mut projectile = action.entity_mut(PROJECTILE_ID);
+        projectile.insert_position(Coord::new(0, 0));
+        projectile.insert_projectile();
+        projectile.insert_destroy_on_collision();
+        projectile.insert_projectile_damage(Damage::physical(1));
+        if piercing {
+            projectile.insert_piercing();
+        }
+    }
+
+    {
+        let mut target = action.entity_mut(TARGET_ID);
+        target.insert_position(Coord::new(1, 0));
+        target.insert_projectile_collider();
+        target.insert_hit_points(HitPoints::new(10));
+    }
+
+    let env = env_with(action, 3, 1);
+
+    let mut action = EcsAction::new();
+    action.insert_position(PROJECTILE_ID, Coord::new(1, 0));
+
+    let mut reactions = Vec::new();
+    let result = rules::projectile_collision_trigger(env.rule_env(), &action, &mut reactions);
+
+    (result, reactions)
+}
+
+fn is_collision_with_target(args: ActionArgs) -> bool {
+    match args {
+        ActionArgs::ProjectileCollision(ProjectileCollision { projectile_id, collider_id }) => {
+            projectile_id == PROJECTILE_ID && collider_id == TARGET_ID
+        }
+        _ => false,
+    }
+}
+
+#[test]
+fn piercing_projectiles_pass_through_their_first_target() {
+    let (result, reactions) = projectile_hits_target(true);
+
+    // the projectile keeps moving, and still collides with the target
+    assert!(result.is_ok());
+    assert_eq!(reactions.len(), 1);
+    assert!(is_collision_with_target(reactions[0].action));
+}
+
+#[test]
+fn other_projectiles_stop_at_their_first_target() {
+    let (result, reactions) = projectile_hits_target(false);
+
+    // the move is replaced by the collision
+    match result {
+        Err(RuleResolution::Consume(args)) => assert!(is_collision_with_target(args)),
+        other => panic!("Expected the move to be consumed, found {:?}", other),
+    }
+    assert!(reactions.iter().all(|reaction| {
+        match reaction.action {
+            ActionArgs::Destroy(entity_id) => entity_id == PROJECTILE_ID,
+            _ => false,
+        }
+    }));
+}
+
+const KNOCKED_BACK_ID: EntityId = 0;
+const OBSTACLE_ID: EntityId = 1;
+
+/// Checks the rules which can stop a move, in the order they are registered,
+/// against a character being knocked into an obstacle, returning the result
+/// of the first rule to reject the move and the reactions
+fn knocked_into<F: FnOnce(&mut EcsAction)>(populate_obstacle: F) -> (RuleResult, Vec<Reaction>) {
+    let mut action = EcsAction::new();
+
+    {
+        let mut entity = action.entity_mut(KNOCKED_BACK_ID);
+        entity.insert_position(Coord::new(0, 0));
+        entity.insert_collider();
+        entity.insert_hit_points(HitPoints::new(10));
+        entity.insert_door_opener();
+        entity.insert_bump_attacker(MeleeAttack::new(Damage::physical(1), 1.0, 0.0, 0));
+    }
+
+    action.insert_position(OBSTACLE_ID, Coord::new(1, 0));
+    populate_obstacle(&mut action);
+
+    let env = env_with(action, 3, 1);
+
+    let mut action = EcsAction::new();
+    action.insert_position(KNOCKED_BACK_ID, Coord::new(1, 0));
+    action.set_knockback(Knockback::new(KNOCKED_BACK_ID, Direction::East, 1));
+
+    let rules: [RuleFn; 3] = [rules::open_door, rules::bump_attack, rules::collision];
+
+    let mut reactions = Vec::new();
+    for rule in rules.iter() {
+        let result = rule(env.rule_env(), &action, &mut reactions);
+        if result.is_err() {
+            return (result, reactions);
+        }
+    }
+
+    (RULE_ACCEPT, reactions)
+}
+
+/// The entities damaged by reactions, and whether the entity which was
+/// knocked back was stunned
+fn impact(reactions: &[Reaction]) -> (Vec<EntityId>, bool) {
+    let mut damaged = Vec::new();
+    let mut stunned = false;
+
+    for reaction in reactions {
+        match reaction.action {
+            ActionArgs::Damage(entity_id, damage, _) => {
+                assert_eq!(damage, KNOCKBACK_IMPACT_DAMAGE);
+                damaged.push(entity_id);
+            }
+            ActionArgs::ApplyStatusEffect(entity_id, effect) => {
+                stunned |= entity_id == KNOCKED_BACK_ID && effect.effect_type == StatusEffectType::Stunned;
+            }
+            _ => {}
+        }
+    }
+
+    damaged.sort();
+    (damaged, stunned)
+}
+
+#[test]
+fn knockback_into_a_wall_hurts() {
+    let (result, reactions) = knocked_into(|action| action.insert_solid(OBSTACLE_ID));
+
+    assert!(result.is_err());
+    assert_eq!(impact(&reactions), (vec![KNOCKED_BACK_ID], true));
+}
+
+#[test]
+fn knockback_into_a_character_hurts_both() {
+    let (result, reactions) = knocked_into(|action| {
+        action.insert_enemy(OBSTACLE_ID);
+        action.insert_bump_attackable(OBSTACLE_ID);
+        action.insert_hit_points(OBSTACLE_ID, HitPoints::new(10));
+    });
+
+    assert!(result.is_err());
+    assert_eq!(impact(&reactions), (vec![KNOCKED_BACK_ID, OBSTACLE_ID], true));
+}
+
+#[test]
+fn knockback_into_a_closed_door_hurts_without_opening_it() {
+    let (result, reactions) = knocked_into(|action| {
+        action.insert_solid(OBSTACLE_ID);
+        action.insert_door_state(OBSTACLE_ID, DoorState::Closed);
+        action.insert_bump_attackable(OBSTACLE_ID);
+        action.insert_hit_points(OBSTACLE_ID, HitPoints::new(3));
+        action.insert_destructible(OBSTACLE_ID, TileType::BrokenDoor);
+    });
+
+    assert!(result.is_err());
+    assert_eq!(impact(&reactions), (vec![KNOCKED_BACK_ID], true));
+    assert!(reactions.iter().all(|reaction| {
+        match reaction.action {
+            ActionArgs::OpenDoor(_) | ActionArgs::MeleeAttack(..) => false,
+            _ => true,
+        }
+    }));
+}
+
+const ATTACKER_ID: EntityId = 0;
+const VICTIM_ID: EntityId = 1;
+
+/// Checks the bump attack rule against a terrified attacker walking into
+/// a victim, returning the result and the reactions
+fn terrified_bump<F: FnOnce(&mut EcsAction)>(populate_victim: F) -> (RuleResult, Vec<Reaction>) {
+    let mut terrified = StatusEffects::new();
+    terrified.apply(StatusEffect::new(StatusEffectType::Terrified, 3));
+
+    let mut action = EcsAction::new();
+
+    {
+        let mut attacker = action.entity_mut(ATTACKER_ID);
+        attacker.insert_position(Coord::new(0, 0));
+        attacker.insert_bump_attacker(MeleeAttack::new(Damage::physical(1), 1.0, 0.0, 0));
+        attacker.insert_status_effects(terrified);
+    }
+
+    {
+        let mut victim = action.entity_mut(VICTIM_ID);
+        victim.insert_position(Coord::new(1, 0));
+        victim.insert_bump_attackable();
+        victim.insert_hit_points(HitPoints::new(10));
+    }
+    populate_victim(&mut action);
+
+    let env = env_with(action, 2, 1);
+
+    let mut action = EcsAction::new();
+    action.insert_position(ATTACKER_ID, Coord::new(1, 0));
+
+    let mut reactions = Vec::new();
+    let result = rules::bump_attack(env.rule_env(), &action, &mut reactions);
+
+    (result, reactions)
+}
+
+#[test]
+fn terrified_attackers_cower() {
+    let (result, reactions) = terrified_bump(|action| action.insert_enemy(VICTIM_ID));
+
+    // the attack is replaced by an explanation
+    assert!(result.is_err());
+    assert_eq!(reactions.len(), 1);
+    match reactions[0].action {
+        ActionArgs::Cower(entity_id) => assert_eq!(entity_id, ATTACKER_ID),
+        other => panic!("Expected the attacker to cower, found {:?}", other),
+    }
+}
+
+#[test]
+fn terrified_attackers_are_blocked_by_terrain() {
+    let (result, reactions) = terrified_bump(|action| {
+        action.insert_solid(VICTIM_ID);
+        action.insert_destructible(VICTIM_ID, TileType::TreeStump);
+    });
+
+    // walking into a tree is neither an attack nor cowering
+    assert!(result.is_err());
+    assert!(reactions.is_empty());
+}
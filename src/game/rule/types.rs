@@ -6,6 +6,7 @@ use spatial_hash::*;
 
 pub type RuleResult = result::Result<RuleResolution, RuleResolution>;
 
+#[derive(Debug, Clone, Copy)]
 pub enum RuleResolution {
     Accept,
     Reject,
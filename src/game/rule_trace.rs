@@ -0,0 +1,111 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use game::*;
+use debug;
+
+/// Number of checked actions kept for the trace viewer
+pub const RULE_TRACE_MAX_ENTRIES: usize = 256;
+
+/// The outcome of checking a single rule against an action
+#[derive(Debug, Clone)]
+pub struct RuleTraceRule {
+    pub rule: RuleId,
+    pub resolution: RuleResolution,
+    /// Actions the rule queued in response
+    pub reactions: Vec<ActionArgs>,
+}
+
+/// Every rule checked against an action, in order
+#[derive(Debug, Clone)]
+pub struct RuleTraceEntry {
+    pub action_id: ActionId,
+    pub action: ActionArgs,
+    pub rules: Vec<RuleTraceRule>,
+    pub resolution: Option<RuleResolution>,
+}
+
+impl fmt::Display for RuleTraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "action {}: {:?}", self.action_id, self.action)?;
+        if let Some(resolution) = self.resolution {
+            write!(f, " => {:?}", resolution)?;
+        }
+
+        for rule in self.rules.iter() {
+            write!(f, "\n  {:?}: {:?}", rule.rule, rule.resolution)?;
+            for reaction in rule.reactions.iter() {
+                write!(f, "\n    queued {:?}", reaction)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Records which rules accepted, rejected or consumed each action, along
+/// with the reactions they queued. Entries are written to the debug output
+/// as they are completed, and the most recent are kept for the trace viewer.
+pub struct RuleTrace {
+    entries: VecDeque<RuleTraceEntry>,
+    max_entries: usize,
+}
+
+impl RuleTrace {
+    pub fn new(max_entries: usize) -> Self {
+        RuleTrace {
+            entries: VecDeque::new(),
+            max_entries: max_entries,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(0)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_entries > 0
+    }
+
+    pub fn entries(&self) -> &VecDeque<RuleTraceEntry> {
+        &self.entries
+    }
+
+    /// Called before the rules are checked against an action
+    pub fn begin_action(&mut self, action_id: ActionId, action: ActionArgs) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        while self.entries.len() >= self.max_entries {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(RuleTraceEntry {
+            action_id: action_id,
+            action: action,
+            rules: Vec::new(),
+            resolution: None,
+        });
+    }
+
+    pub fn record_rule(&mut self, rule: RuleId, resolution: RuleResolution, reactions: &[Reaction]) {
+        if let Some(entry) = self.entries.back_mut() {
+            entry.rules.push(RuleTraceRule {
+                rule: rule,
+                resolution: resolution,
+                reactions: reactions.iter().map(|reaction| reaction.action).collect(),
+            });
+        }
+    }
+
+    /// Called once the rules have decided what happens to an action
+    pub fn end_action(&mut self, resolution: RuleResolution) {
+        if let Some(entry) = self.entries.back_mut() {
+            entry.resolution = Some(resolution);
+
+            // the debug output isn't available in every frontend
+            let _ = debug::write(format!("{}\n", entry).as_bytes());
+        }
+    }
+}
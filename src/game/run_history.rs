@@ -0,0 +1,77 @@
+use game::*;
+
+/// Points awarded for each level reached
+pub const SCORE_PER_LEVEL: u64 = 100;
+
+/// Points awarded for each enemy killed
+pub const SCORE_PER_KILL: u64 = 10;
+
+pub fn score(depth: usize, kills: u64) -> u64 {
+    depth as u64 * SCORE_PER_LEVEL + kills * SCORE_PER_KILL
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunOutcome {
+    Died,
+}
+
+/// Summary of a finished game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRecord {
+    pub seed: usize,
+    pub outcome: RunOutcome,
+    pub cause_of_death: Option<DeathCause>,
+    /// Number of levels reached
+    pub depth: usize,
+    pub turns: u64,
+    pub kills: u64,
+    pub score: u64,
+    /// Seconds since the unix epoch
+    pub finished: u64,
+}
+
+impl RunRecord {
+    pub fn new(seed: usize, outcome: RunOutcome, game_state: &GameState) -> Self {
+        let stats = game_state.stats();
+        let depth = game_state.num_levels();
+
+        RunRecord {
+            seed: seed,
+            outcome: outcome,
+            cause_of_death: stats.killed_by,
+            depth: depth,
+            turns: stats.pc_turns,
+            kills: stats.enemies_killed,
+            score: score(depth, stats.enemies_killed),
+            finished: unix_now(),
+        }
+    }
+}
+
+/// Every finished game, in the order they were finished
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RunHistory {
+    runs: Vec<RunRecord>,
+}
+
+impl RunHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, record: RunRecord) {
+        self.runs.push(record);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Runs ordered from highest to lowest score. Runs with equal scores
+    /// are ordered from earliest to latest.
+    pub fn high_scores(&self) -> Vec<&RunRecord> {
+        let mut runs: Vec<&RunRecord> = self.runs.iter().collect();
+        runs.sort_by(|a, b| b.score.cmp(&a.score));
+        runs
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use game::*;
+use game::game_file::{FileError, FileResult};
+
+/// Name given to a history which couldn't be parsed when it is moved aside
+const CORRUPT_HISTORY_STEM: &'static str = "history-corrupt";
+
+/// Returns an empty history if no runs have been recorded
+pub fn load<P: AsRef<Path>>(user_path: P) -> FileResult<RunHistory> {
+    match game_file::read_json(user_path.as_ref().join(user_files::HISTORY)) {
+        Err(FileError::MissingFile) => Ok(RunHistory::new()),
+        other => other,
+    }
+}
+
+/// Adds a finished game to the history. A history which can't be parsed is
+/// moved aside rather than overwritten, and a new history is started, in
+/// which case the path it was moved to is returned.
+pub fn append<P: AsRef<Path>>(user_path: P, record: RunRecord) -> FileResult<Option<PathBuf>> {
+    let user_path = user_path.as_ref();
+    let history_path = user_path.join(user_files::HISTORY);
+
+    let (mut history, moved_to) = match load(user_path) {
+        Ok(history) => (history, None),
+        Err(FileError::InvalidFormat) => {
+            let moved_to = user_files::unique_path(user_path, CORRUPT_HISTORY_STEM, "json");
+            fs::rename(&history_path, &moved_to).map_err(|_| FileError::FailedToWrite)?;
+            (RunHistory::new(), Some(moved_to))
+        }
+        Err(e) => return Err(e),
+    };
+
+    history.push(record);
+    game_file::write_json(history_path, &history)?;
+
+    Ok(moved_to)
+}
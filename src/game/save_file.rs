@@ -1,34 +1,214 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::fs::{self, File};
 use std::io::{Read, Write};
+use std::hash::Hasher;
 use bincode::{self, SizeLimit};
+use serde_json;
 use game::*;
+use game::game_file::FileError;
+use util::StableHasher;
 
-const SAVE_FILE: &'static str = "save";
+/// Identifies a file as a save file
+pub const SAVE_MAGIC: &'static [u8] = b"HOWLSAVE";
 
-pub fn save<P: AsRef<Path>>(user_path: P, game_state: GameState) -> GameState {
+/// Increment whenever a change to the game state changes how it is
+/// serialized, and add a case to `migrate` which reads the previous version.
+pub const SAVE_FORMAT_VERSION: u32 = 2;
+
+/// The version of saves written before the header was introduced
+const HEADERLESS_SAVE_FORMAT_VERSION: u32 = 1;
+
+/// A save file consists of the magic number, followed by a header, followed
+/// by the serialized game state. The checksum is computed over the
+/// serialized game state.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SaveHeader {
+    version: u32,
+    checksum: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SaveFileError {
+    MissingFile,
+    Unreadable,
+    NotASaveFile,
+    UnsupportedVersion(u32),
+    ChecksumMismatch,
+    InvalidData,
+    FailedToWrite,
+}
+
+pub type SaveFileResult<T> = Result<T, SaveFileError>;
+
+fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Number of previous saves kept alongside the current save
+pub const NUM_SAVE_BACKUPS: usize = 3;
+
+fn backup_path<P: AsRef<Path>>(slot_path: P, backup: usize) -> PathBuf {
+    slot_path.as_ref().join(format!("{}.{}", user_files::SAVE, backup))
+}
+
+/// Makes room for a new save by shifting each backup along by one, then
+/// copying the current save into the first backup. The current save is
+/// left in place until the new save replaces it.
+fn rotate_backups<P: AsRef<Path>>(slot_path: P) -> SaveFileResult<()> {
+    let slot_path = slot_path.as_ref();
+    let save_path = slot_path.join(user_files::SAVE);
+
+    if !save_path.is_file() {
+        return Ok(());
+    }
+
+    for backup in (1..NUM_SAVE_BACKUPS).rev() {
+        let from = backup_path(slot_path, backup);
+        if from.is_file() {
+            fs::rename(from, backup_path(slot_path, backup + 1)).map_err(|_| SaveFileError::FailedToWrite)?;
+        }
+    }
+
+    fs::copy(save_path, backup_path(slot_path, 1)).map_err(|_| SaveFileError::FailedToWrite)?;
+
+    Ok(())
+}
+
+/// The save is written to a temporary file which is then renamed over the
+/// current save, so an interrupted write never leaves a truncated save.
+fn write_serializable<P: AsRef<Path>>(slot_path: P, serializable: &SerializableGameState) -> SaveFileResult<()> {
+    let slot_path = slot_path.as_ref();
+
+    let payload = bincode::serialize(serializable, SizeLimit::Infinite).map_err(|_| SaveFileError::InvalidData)?;
+
+    let header = SaveHeader {
+        version: SAVE_FORMAT_VERSION,
+        checksum: checksum(&payload),
+    };
+
+    let mut encoded = SAVE_MAGIC.to_vec();
+    encoded.extend(bincode::serialize(&header, SizeLimit::Infinite).map_err(|_| SaveFileError::InvalidData)?);
+    encoded.extend(payload);
+
+    fs::create_dir_all(slot_path).map_err(|_| SaveFileError::FailedToWrite)?;
+
+    let tmp_path = slot_path.join(format!("{}.tmp", user_files::SAVE));
+    File::create(&tmp_path)
+        .and_then(|mut f| f.write_all(&encoded).and_then(|_| f.sync_all()))
+        .map_err(|_| SaveFileError::FailedToWrite)?;
+
+    rotate_backups(slot_path)?;
+
+    fs::rename(tmp_path, slot_path.join(user_files::SAVE)).map_err(|_| SaveFileError::FailedToWrite)
+}
+
+/// Writes the game state to a slot directory. The game state is handed
+/// back whether or not it could be saved.
+pub fn save<P: AsRef<Path>>(slot_path: P, game_state: GameState) -> (GameState, SaveFileResult<()>) {
     let serializable = SerializableGameState::from(game_state);
 
-    let encoded = bincode::serialize(&serializable, SizeLimit::Infinite).expect("Failed to serialize game state");
+    let result = write_serializable(slot_path, &serializable);
 
-    File::create(user_path.as_ref().join(SAVE_FILE))
-        .and_then(|mut f| f.write_all(&encoded))
-        .expect("Failed to save game");
+    (GameState::from(serializable), result)
+}
 
-    GameState::from(serializable)
+/// Converts the serialized game state from a given format version into the
+/// current version
+fn migrate(version: u32, payload: &[u8]) -> SaveFileResult<SerializableGameState> {
+    match version {
+        SAVE_FORMAT_VERSION => {
+            bincode::deserialize(payload).map_err(|_| SaveFileError::InvalidData)
+        }
+        // saves written before the header was introduced hold entities with
+        // components which have since changed, so they can't be read
+        HEADERLESS_SAVE_FORMAT_VERSION => Err(SaveFileError::UnsupportedVersion(version)),
+        other => Err(SaveFileError::UnsupportedVersion(other)),
+    }
 }
 
-pub fn load<P: AsRef<Path>>(user_path: P) -> Option<GameState> {
-    if let Ok(mut f) = File::open(user_path.as_ref().join(SAVE_FILE)) {
-        let mut encoded = Vec::new();
-        f.read_to_end(&mut encoded).expect("Failed to read save file");
-        let serializable: SerializableGameState = bincode::deserialize(&encoded).expect("Failed to parse save file");
-        Some(GameState::from(serializable))
+fn read_serializable<P: AsRef<Path>>(path: P) -> SaveFileResult<SerializableGameState> {
+    let mut f = File::open(path).map_err(|_| SaveFileError::MissingFile)?;
+    let mut encoded = Vec::new();
+    f.read_to_end(&mut encoded).map_err(|_| SaveFileError::Unreadable)?;
+
+    if encoded.starts_with(SAVE_MAGIC) {
+        let mut reader = &encoded[SAVE_MAGIC.len()..];
+        let header: SaveHeader = bincode::deserialize_from(&mut reader, SizeLimit::Infinite)
+            .map_err(|_| SaveFileError::NotASaveFile)?;
+
+        if header.version > SAVE_FORMAT_VERSION {
+            return Err(SaveFileError::UnsupportedVersion(header.version));
+        }
+
+        if checksum(reader) != header.checksum {
+            return Err(SaveFileError::ChecksumMismatch);
+        }
+
+        migrate(header.version, reader)
     } else {
-        None
+        migrate(HEADERLESS_SAVE_FORMAT_VERSION, &encoded)
+    }
+}
+
+/// Reads the current save, falling back to the most recent backup which can
+/// be read if the current save is missing or damaged. If a backup was used,
+/// the reason the current save couldn't be read is returned alongside it.
+fn read_serializable_or_backup<P: AsRef<Path>>(slot_path: P) -> SaveFileResult<(SerializableGameState, Option<SaveFileError>)> {
+    let slot_path = slot_path.as_ref();
+
+    let error = match read_serializable(slot_path.join(user_files::SAVE)) {
+        Ok(serializable) => return Ok((serializable, None)),
+        Err(error) => error,
+    };
+
+    for backup in 1..NUM_SAVE_BACKUPS + 1 {
+        if let Ok(serializable) = read_serializable(backup_path(slot_path, backup)) {
+            return Ok((serializable, Some(error)));
+        }
     }
+
+    Err(error)
+}
+
+/// Loads the game saved in a slot directory. If the current save couldn't be
+/// read and the game was restored from a backup, the reason is returned
+/// alongside the game state.
+pub fn load<P: AsRef<Path>>(slot_path: P) -> SaveFileResult<(GameState, Option<SaveFileError>)> {
+    read_serializable_or_backup(slot_path).map(|(serializable, error)| (GameState::from(serializable), error))
+}
+
+/// Writes the save in a slot directory to a file in a human-readable
+/// format which can be edited and imported. If the current save couldn't be
+/// read and a backup was exported instead, the reason is returned.
+pub fn export<P: AsRef<Path>, Q: AsRef<Path>>(slot_path: P, path: Q) -> SaveFileResult<Option<SaveFileError>> {
+    let (serializable, error) = read_serializable_or_backup(slot_path)?;
+    let string = serde_json::to_string_pretty(&serializable).map_err(|_| SaveFileError::InvalidData)?;
+
+    game_file::write_string(path, string).map_err(|_| SaveFileError::FailedToWrite)?;
+
+    Ok(error)
 }
 
-pub fn delete<P: AsRef<Path>>(user_path: P) -> bool {
-    fs::remove_file(user_path.as_ref().join(SAVE_FILE)).is_ok()
+/// Replaces the save in a slot directory with a file written by `export`
+pub fn import<P: AsRef<Path>, Q: AsRef<Path>>(slot_path: P, path: Q) -> SaveFileResult<()> {
+    let string = game_file::read_string(path).map_err(|e| match e {
+        FileError::MissingFile => SaveFileError::MissingFile,
+        _ => SaveFileError::Unreadable,
+    })?;
+    let serializable: SerializableGameState = serde_json::from_str(&string).map_err(|_| SaveFileError::InvalidData)?;
+
+    write_serializable(slot_path, &serializable)
+}
+
+/// Deletes the save along with its backups
+pub fn delete<P: AsRef<Path>>(slot_path: P) -> bool {
+    let slot_path = slot_path.as_ref();
+
+    for backup in 1..NUM_SAVE_BACKUPS + 1 {
+        let _ = fs::remove_file(backup_path(slot_path, backup));
+    }
+
+    fs::remove_file(slot_path.join(user_files::SAVE)).is_ok()
 }
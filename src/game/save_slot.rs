@@ -0,0 +1,93 @@
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::ops::Range;
+
+use game::*;
+use game::save_file::SaveFileResult;
+
+/// Number of save slots in each profile. Slots are numbered from 1.
+pub const NUM_SAVE_SLOTS: usize = 3;
+
+/// Details of a saved game shown in the slot picker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveSlotInfo {
+    pub pc_turns: u64,
+    pub level_id: LevelId,
+    /// Seconds since the unix epoch
+    pub last_played: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SaveSlotSummary {
+    Empty,
+    Saved(SaveSlotInfo),
+    /// The slot contains a save, but its details weren't recorded
+    SavedWithoutInfo,
+}
+
+impl SaveSlotInfo {
+    pub fn new(game_state: &GameState) -> Self {
+        SaveSlotInfo {
+            pc_turns: game_state.stats().pc_turns,
+            level_id: game_state.level_id().unwrap_or(0),
+            last_played: unix_now(),
+        }
+    }
+}
+
+pub fn slot_path<P: AsRef<Path>>(profile_path: P, slot: usize) -> PathBuf {
+    profile_path.as_ref().join(user_files::SLOTS).join(slot.to_string())
+}
+
+pub fn slots() -> Range<usize> {
+    1..NUM_SAVE_SLOTS + 1
+}
+
+pub fn summary<P: AsRef<Path>>(profile_path: P, slot: usize) -> SaveSlotSummary {
+    let slot_path = slot_path(profile_path, slot);
+
+    if !slot_path.join(user_files::SAVE).is_file() {
+        return SaveSlotSummary::Empty;
+    }
+
+    match game_file::read_toml(slot_path.join(user_files::SLOT_INFO)) {
+        Ok(info) => SaveSlotSummary::Saved(info),
+        Err(_) => SaveSlotSummary::SavedWithoutInfo,
+    }
+}
+
+pub fn is_any_occupied<P: AsRef<Path>>(profile_path: P) -> bool {
+    slots().any(|slot| summary(profile_path.as_ref(), slot) != SaveSlotSummary::Empty)
+}
+
+/// The occupied slot which was played most recently
+pub fn most_recent<P: AsRef<Path>>(profile_path: P) -> Option<usize> {
+    slots().filter_map(|slot| {
+        match summary(profile_path.as_ref(), slot) {
+            SaveSlotSummary::Empty => None,
+            SaveSlotSummary::Saved(info) => Some((info.last_played, slot)),
+            SaveSlotSummary::SavedWithoutInfo => Some((0, slot)),
+        }
+    }).max_by_key(|&(last_played, slot)| (last_played, NUM_SAVE_SLOTS - slot))
+        .map(|(_, slot)| slot)
+}
+
+/// Writes the game state to a slot. The game state is handed back whether or
+/// not it could be saved.
+pub fn save<P: AsRef<Path>>(profile_path: P, slot: usize, game_state: GameState) -> (GameState, SaveFileResult<()>) {
+    let slot_path = slot_path(profile_path, slot);
+    let info = SaveSlotInfo::new(&game_state);
+
+    let (game_state, result) = save_file::save(&slot_path, game_state);
+
+    if result.is_ok() {
+        // the info is only used for display, so failing to write it isn't fatal
+        let _ = game_file::write_toml(slot_path.join(user_files::SLOT_INFO), &info);
+    }
+
+    (game_state, result)
+}
+
+pub fn delete<P: AsRef<Path>>(profile_path: P, slot: usize) -> bool {
+    fs::remove_dir_all(slot_path(profile_path, slot)).is_ok()
+}
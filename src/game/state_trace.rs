@@ -0,0 +1,113 @@
+use std::fmt;
+
+use game::state_trace_file::StateTraceWriter;
+
+/// The hash of the game state at the end of a turn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateTraceEntry {
+    pub turn_id: u64,
+    pub hash: u64,
+}
+
+/// The first point at which a run differs from a reference trace.
+/// `expected` is `None` if the run continued past the end of the reference,
+/// and `actual` is `None` if the run ended before the reference did.
+#[derive(Debug, Clone, Copy)]
+pub struct StateDivergence {
+    pub index: usize,
+    pub expected: Option<StateTraceEntry>,
+    pub actual: Option<StateTraceEntry>,
+}
+
+impl fmt::Display for StateDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.expected, self.actual) {
+            (Some(expected), Some(actual)) => {
+                write!(f, "Game state diverged from reference at turn {} (expected {:016x}, found {:016x})",
+                       actual.turn_id, expected.hash, actual.hash)
+            }
+            (None, Some(actual)) => {
+                write!(f, "Game continued past the end of the reference at turn {}", actual.turn_id)
+            }
+            (Some(expected), None) => {
+                write!(f, "Game ended before reaching turn {} of the reference", expected.turn_id)
+            }
+            (None, None) => {
+                write!(f, "Game state diverged from reference after {} turns", self.index)
+            }
+        }
+    }
+}
+
+/// Records the hash of the game state after each turn to a trace file, and
+/// optionally compares it against a trace recorded during a previous run.
+pub struct StateTrace {
+    writer: Option<StateTraceWriter>,
+    reference: Option<Vec<StateTraceEntry>>,
+    num_recorded: usize,
+    divergence: Option<StateDivergence>,
+}
+
+impl StateTrace {
+    pub fn new(writer: Option<StateTraceWriter>, reference: Option<Vec<StateTraceEntry>>) -> Self {
+        StateTrace {
+            writer: writer,
+            reference: reference,
+            num_recorded: 0,
+            divergence: None,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(None, None)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.writer.is_some() || self.reference.is_some()
+    }
+
+    pub fn record(&mut self, turn_id: u64, hash: u64) {
+        let entry = StateTraceEntry {
+            turn_id: turn_id,
+            hash: hash,
+        };
+
+        if let Some(ref mut writer) = self.writer {
+            // failing to record shouldn't interrupt the game
+            let _ = writer.write_entry(entry);
+        }
+
+        if self.divergence.is_none() {
+            if let Some(ref reference) = self.reference {
+                let expected = reference.get(self.num_recorded).cloned();
+                if expected != Some(entry) {
+                    self.divergence = Some(StateDivergence {
+                        index: self.num_recorded,
+                        expected: expected,
+                        actual: Some(entry),
+                    });
+                }
+            }
+        }
+
+        self.num_recorded += 1;
+    }
+
+    /// Returns the first divergence from the reference trace, including the
+    /// case where the reference is longer than the run so far
+    pub fn divergence(&self) -> Option<StateDivergence> {
+        if self.divergence.is_some() {
+            return self.divergence;
+        }
+
+        self.reference.as_ref().and_then(|reference| {
+            reference.get(self.num_recorded).map(|&expected| {
+                StateDivergence {
+                    index: self.num_recorded,
+                    expected: Some(expected),
+                    actual: None,
+                }
+            })
+        })
+    }
+}
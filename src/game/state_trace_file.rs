@@ -0,0 +1,51 @@
+use std::path::Path;
+use std::fs::File;
+use std::io::Write;
+
+use game::*;
+use game::game_file::{FileError, FileResult};
+
+/// A state trace file contains one line per turn, consisting of the turn id
+/// followed by the hash of the game state in hexadecimal. Traces from two runs
+/// can also be compared with a text diff tool.
+pub struct StateTraceWriter {
+    file: File,
+}
+
+impl StateTraceWriter {
+    pub fn create<P: AsRef<Path>>(path: P) -> FileResult<Self> {
+        let file = File::create(path).map_err(|_| FileError::FailedToWrite)?;
+
+        Ok(StateTraceWriter {
+            file: file,
+        })
+    }
+
+    pub fn write_entry(&mut self, entry: StateTraceEntry) -> FileResult<()> {
+        writeln!(self.file, "{} {:016x}", entry.turn_id, entry.hash).map_err(|_| FileError::FailedToWrite)
+    }
+}
+
+pub fn load<P: AsRef<Path>>(path: P) -> FileResult<Vec<StateTraceEntry>> {
+    let string = game_file::read_string(path)?;
+
+    let mut entries = Vec::new();
+    for line in string.lines() {
+        let mut fields = line.split_whitespace();
+
+        let turn_id = fields.next().and_then(|s| s.parse::<u64>().ok());
+        let hash = fields.next().and_then(|s| u64::from_str_radix(s, 16).ok());
+
+        match (turn_id, hash, fields.next()) {
+            (Some(turn_id), Some(hash), None) => {
+                entries.push(StateTraceEntry {
+                    turn_id: turn_id,
+                    hash: hash,
+                });
+            }
+            _ => return Err(FileError::InvalidFormat),
+        }
+    }
+
+    Ok(entries)
+}
@@ -41,7 +41,7 @@ fn level_str() -> Vec<&'static str> {
     vec!["&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&&",
          "&,,,,,,,,,,,,,,,,,,,,,,,,,,,,,,,,,,,,&",
          "&,,############################,,,,,,&",
-         "&,,#.........#................#,,&,,,&",
+         "&,,#.........%................#,,&,,,&",
          "&,,#.........#................#,,,&,,&",
          "&,,#..........................#,,&,,,&",
          "&&,#.........#................#,,,,,,&",
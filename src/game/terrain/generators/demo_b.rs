@@ -33,7 +33,7 @@ fn level_str() -> Vec<&'static str> {
          "#####.....############..................#",
          "#....................#..................#",
          "#....................#........#.........#",
-         "#.............................#.........#",
+         "#...........h.................#.........#",
          "#....................#........#.........#",
          "#....................#........#.........#",
          "#....................#........#.........#",
@@ -41,7 +41,7 @@ fn level_str() -> Vec<&'static str> {
          "#............#...................#......#",
          "#............#...................#......#",
          "#............#..........................#",
-         "#................................#......#",
+         "#........................h.......#......#",
          "#............#...................#......#",
          "#............#...................#......#",
          "#########################################",]
@@ -22,6 +22,10 @@ pub fn terrain_from_strings<S: TurnScheduleQueue>(strings: &[&str],
                     prototypes::wall(g.entity_mut(ids.new_id()), coord);
                     prototypes::floor(g.entity_mut(ids.new_id()), coord);
                 }
+                '%' => {
+                    prototypes::cracked_wall(g.entity_mut(ids.new_id()), coord);
+                    prototypes::floor(g.entity_mut(ids.new_id()), coord);
+                }
                 '&' => {
                     prototypes::tree(g, ids, coord);
 
@@ -51,6 +55,15 @@ pub fn terrain_from_strings<S: TurnScheduleQueue>(strings: &[&str],
                     let ticket = schedule.schedule_turn(id, turn_offset);
                     g.insert_schedule_ticket(id, ticket);
                 }
+                'h' => {
+                    prototypes::floor(g.entity_mut(ids.new_id()), coord);
+                    let id = ids.new_id();
+                    prototypes::hunter(g.entity_mut(id), coord);
+
+                    let turn_offset = g.turn_offset(id).expect("Expected component turn_offset");
+                    let ticket = schedule.schedule_turn(id, turn_offset);
+                    g.insert_schedule_ticket(id, ticket);
+                }
                 _ => panic!(),
             }
             x += 1;
@@ -0,0 +1,33 @@
+use ecs::*;
+use spatial_hash::*;
+use game::*;
+
+/// The entity store and spatial hash of a small level, for tests which
+/// check rules or behaviour against a handful of entities
+pub struct TestEnv {
+    pub ecs: EcsCtx,
+    pub spatial_hash: SpatialHashTable,
+}
+
+impl TestEnv {
+    pub fn rule_env(&self) -> RuleEnv {
+        RuleEnv {
+            ecs: &self.ecs,
+            spatial_hash: &self.spatial_hash,
+        }
+    }
+}
+
+/// Commits an action populating an empty level of the given size
+pub fn env_with(mut action: EcsAction, width: usize, height: usize) -> TestEnv {
+    let mut ecs = EcsCtx::new();
+    let mut spatial_hash = SpatialHashTable::new(width, height);
+
+    spatial_hash.update(&ecs, &action, 0);
+    ecs.commit(&mut action);
+
+    TestEnv {
+        ecs: ecs,
+        spatial_hash: spatial_hash,
+    }
+}
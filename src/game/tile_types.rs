@@ -1,16 +1,25 @@
 #[derive(Hash, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TileType {
     Wall,
+    CrackedWall,
+    Rubble,
     Tree,
     DeadTree,
+    TreeStump,
     Floor,
     Ground,
     OpenDoor,
     ClosedDoor,
+    BrokenDoor,
     Bullet,
+    Pellet,
+    PiercingRound,
+    ExplosiveRound,
+    SilverBullet,
     Player,
     TerrorPillar,
     TerrorFly,
+    Hunter,
     Book,
     UpStairs,
     DownStairs,
@@ -20,6 +29,7 @@ impl TileType {
     pub fn opaque_bg(self) -> bool {
         match self {
             TileType::Wall
+                | TileType::CrackedWall
                 | TileType::Floor
                 | TileType::Ground
                 | TileType::ClosedDoor
@@ -30,7 +40,7 @@ impl TileType {
 
     pub fn has_front_variant(self) -> bool {
         match self {
-            TileType::Wall => true,
+            TileType::Wall | TileType::CrackedWall => true,
             _ => false,
         }
     }
@@ -38,16 +48,25 @@ impl TileType {
     pub fn from_str(s: &str) -> Option<Self> {
         let tile = match s {
             "Wall" => TileType::Wall,
+            "CrackedWall" => TileType::CrackedWall,
+            "Rubble" => TileType::Rubble,
             "Tree" => TileType::Tree,
             "DeadTree" => TileType::DeadTree,
+            "TreeStump" => TileType::TreeStump,
             "Floor" => TileType::Floor,
             "Ground" => TileType::Ground,
             "OpenDoor" => TileType::OpenDoor,
             "ClosedDoor" => TileType::ClosedDoor,
+            "BrokenDoor" => TileType::BrokenDoor,
             "Bullet" => TileType::Bullet,
+            "Pellet" => TileType::Pellet,
+            "PiercingRound" => TileType::PiercingRound,
+            "ExplosiveRound" => TileType::ExplosiveRound,
+            "SilverBullet" => TileType::SilverBullet,
             "Player" => TileType::Player,
             "TerrorPillar" => TileType::TerrorPillar,
             "TerrorFly" => TileType::TerrorFly,
+            "Hunter" => TileType::Hunter,
             "Book" => TileType::Book,
             "UpStairs" => TileType::UpStairs,
             "DownStairs" => TileType::DownStairs,
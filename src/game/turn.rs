@@ -1,15 +1,15 @@
 use std::time::Duration;
-use std::thread;
 use std::cmp;
 use std::cell::RefCell;
 use std::ops::Deref;
 use std::ops::DerefMut;
+use std::mem;
 
 use game::*;
 use game::data::*;
 use ecs::*;
 use spatial_hash::*;
-use util::Schedule;
+use util::{self, Schedule, SerializableSchedule};
 
 const FAILED_ACTION_DELAY: u64 = 16;
 const MIN_TURN_TIME: u64 = 1;
@@ -37,6 +37,8 @@ pub enum TurnResolution {
         level_switch: LevelSwitch,
     },
     GameOver(GameOverReason),
+    /// The entity was removed from the level during its turn
+    Removed(EntityId),
 }
 
 #[derive(PartialEq, Eq)]
@@ -65,14 +67,21 @@ pub struct TurnEnv<'game, 'level: 'game, Renderer: 'game + KnowledgeRenderer> {
     pub ecs: &'level mut EcsCtx,
     pub spatial_hash: &'level mut SpatialHashTable,
     pub behaviour_ctx: &'game BehaviourCtx<Renderer>,
+    pub rules: &'game RuleRegistry,
+    pub rule_trace: &'game mut RuleTrace,
+    pub event_bus: &'game mut EventBus,
     pub rule_reactions: &'game mut Vec<Reaction>,
     pub ecs_action: &'game mut EcsAction,
     pub action_schedule: &'game mut Schedule<ActionArgs>,
     pub turn_schedule: &'game mut TurnSchedule,
     pub pc_observer: &'game Shadowcast,
     pub entity_ids: &'game EntityIdReserver,
+    pub stats: &'game mut GameStats,
     pub rng: &'game GameRng,
     pub language: &'game Box<Language>,
+    pub clock: &'game Box<Clock>,
+    pub journal: &'game mut UndoJournal,
+    pub state_trace: &'game mut StateTrace,
 }
 
 impl<'game> Turn<'game> {
@@ -96,20 +105,91 @@ impl<'game> ActionEnv<'game> {
 impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer> {
     pub fn turn(&mut self) -> GameResult<TurnResolution> {
 
-        self.pc_render(None, Some(ForceRender::IgnoreChange));
+        self.pc_render(&[], Some(ForceRender::IgnoreChange));
+        self.update_tiles_explored();
+
+        if self.is_pc_turn() {
+            self.journal.begin_turn(self.turn_schedule, self.rng, self.stats);
+        }
 
         let resolution = self.take_turn()?;
 
-        match resolution {
+        if self.is_pc_turn() {
+            match resolution {
+                TurnResolution::Exit(..) => {}
+                _ => self.stats.pc_turns += 1,
+            }
+        }
+
+        // status effects count down at the end of every turn the entity
+        // takes, including turns which take it to another level
+        let resolution = match resolution {
             TurnResolution::Schedule(id, ..) => {
                 let delay = self.ecs.turn_time(self.entity_id).expect("Expected turn_time component");
-                Ok(TurnResolution::Schedule(id, delay))
+                let delay = self.ecs.status_effects(self.entity_id).map_or(delay, |s| s.modify_turn_time(delay));
+
+                self.tick_status_effects()?.unwrap_or(TurnResolution::Schedule(id, delay))
+            }
+            level_switch @ TurnResolution::LevelSwitch { .. } => {
+                self.tick_status_effects()?.unwrap_or(level_switch)
             }
-            other => Ok(other),
+            other => other,
+        };
+
+        if self.state_trace.is_enabled() {
+            let hash = self.state_hash();
+            self.state_trace.record(self.turn_id, hash);
         }
+
+        Ok(resolution)
+    }
+
+    /// Counts down the status effects of the entity whose turn just ended.
+    /// Returns a resolution if this ends the turn some other way than
+    /// rescheduling the entity.
+    fn tick_status_effects(&mut self) -> GameResult<Option<TurnResolution>> {
+        if self.ecs.status_effects(self.entity_id).map_or(true, |s| s.is_empty()) {
+            return Ok(None);
+        }
+
+        if let Some(CommitResolution::GameOver(reason)) = self.try_commit_action(ActionArgs::TickStatusEffects(self.entity_id))? {
+            return Ok(Some(TurnResolution::GameOver(reason)));
+        }
+
+        if !self.ecs.contains_turn_time(self.entity_id) {
+            // killed by one of its status effects
+            return Ok(Some(TurnResolution::Removed(self.entity_id)));
+        }
+
+        Ok(None)
+    }
+
+    /// Hash of the parts of the game state which can change during a turn
+    fn state_hash(&mut self) -> u64 {
+        // the ecs and schedule are only serializable by value, so they are
+        // temporarily moved out of the level
+        let ecs = SerializableEcsCtx::from(mem::replace(self.ecs, EcsCtx::new()));
+        let turn_schedule = SerializableSchedule::from(mem::replace(self.turn_schedule, TurnSchedule::new()));
+
+        let hash = util::stable_hash(&(&ecs, &*self.spatial_hash, &turn_schedule, *self.action_id))
+            .expect("Failed to hash game state");
+
+        *self.ecs = EcsCtx::from(ecs);
+        *self.turn_schedule = TurnSchedule::from(turn_schedule);
+
+        hash
     }
 
     fn take_turn(&mut self) -> GameResult<TurnResolution> {
+
+        if self.ecs.status_effects(self.entity_id).map_or(false, |s| s.is_active(StatusEffectType::Stunned)) {
+            // stunned entities lose their turn without choosing an action
+            return Ok(match self.try_commit_action(ActionArgs::Null)? {
+                Some(CommitResolution::GameOver(reason)) => TurnResolution::GameOver(reason),
+                _ => TurnResolution::Schedule(self.entity_id, FAILED_ACTION_DELAY),
+            });
+        }
+
         loop {
             match self.get_meta_action()? {
                 MetaAction::External(external) => {
@@ -120,6 +200,10 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
                     };
                     return Ok(TurnResolution::Exit(reason, self.entity_id));
                 }
+                MetaAction::Rewind => {
+                    self.declare_action_return(false)?;
+                    self.rewind();
+                }
                 MetaAction::ActionArgs(action_args) => {
                     if let Some(resolution) = self.try_commit_action(action_args)? {
                         self.declare_action_return(true)?;
@@ -151,15 +235,29 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
         }
     }
 
+    fn rewind(&mut self) {
+        *self.action_id += 1;
+
+        if self.journal.rewind(self.ecs, self.spatial_hash, self.turn_schedule, self.rng, self.stats, *self.action_id) {
+            self.pc_render(&[], Some(ForceRender::IgnoreChange));
+        }
+    }
+
     fn is_pc_turn(&self) -> bool {
         self.entity_id == self.pc_id
     }
 
-    fn check_rules_wrapper(&mut self) -> RuleResolution {
-        match self.check_rules() {
+    fn check_rules_wrapper(&mut self, action_args: ActionArgs) -> RuleResolution {
+        self.rule_trace.begin_action(*self.action_id, action_args);
+
+        let resolution = match self.check_rules() {
             Ok(res) => res,
             Err(res) => res,
-        }
+        };
+
+        self.rule_trace.end_action(resolution);
+
+        resolution
     }
 
     fn check_rules(&mut self) -> RuleResult {
@@ -170,33 +268,124 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
         };
 
         if self.ecs_action.contains_no_commit() {
-            rules::projectile_collision(rule_env, self.ecs_action, self.rule_reactions)?;
+            self.rules.check(&[RulePhase::NoCommit], rule_env, self.ecs_action, self.rule_reactions, self.rule_trace)
         } else {
-            rules::open_door(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::bump_attack(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::collision(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::projectile_collision_trigger(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::close_door(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::tear_transform(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::realtime_velocity_start(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::realtime_velocity(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::death(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::enemy_collision(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::pc_collision(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::level_switch(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::level_switch_auto(rule_env, self.ecs_action, self.rule_reactions)?;
-            rules::tear_move_transform(rule_env, self.ecs_action, self.rule_reactions)?;
+            self.rules.check(&[RulePhase::PreCommit, RulePhase::Reaction], rule_env, self.ecs_action, self.rule_reactions, self.rule_trace)
         }
+    }
 
-        RULE_ACCEPT
+    /// Works out what the current action will change. Must be called before
+    /// the action is committed.
+    fn summarise(&mut self) {
+        self.event_bus.summarise(self.ecs_action, self.ecs, *self.action_id, self.level_id, self.entity_id, self.pc_id);
     }
 
+    /// Commits the current action, and publishes the summary made by the
+    /// last call to `summarise`
     fn commit(&mut self) {
         self.spatial_hash.update(self.ecs, self.ecs_action, *self.action_id);
-        self.ecs.commit(self.ecs_action);
+
+        if self.journal.is_enabled() {
+            let mut inverse = EcsAction::new();
+            self.ecs.commit_into(self.ecs_action, &mut inverse);
+            self.journal.record(inverse);
+        } else {
+            self.ecs.commit(self.ecs_action);
+        }
+
+        self.event_bus.publish(self.ecs);
     }
 
-    fn pc_render(&mut self, action_description: Option<&ActionDescription>, force: Option<ForceRender>) -> bool {
+    /// Returns the PC's statistics updated to account for the current
+    /// action, or None if the action doesn't affect them. Everything which
+    /// happens during the PC's turn is attributed to the PC. The action must
+    /// have been summarised.
+    fn updated_character_stats(&self) -> Option<CharacterStats> {
+
+        let mut character_stats = if let Some(character_stats) = self.ecs.character_stats(self.pc_id) {
+            character_stats
+        } else {
+            return None;
+        };
+
+        let original = character_stats;
+        let pc_turn = self.is_pc_turn();
+
+        for event in self.event_bus.summary().events.iter() {
+            match *event {
+                GameEvent::Damaged { entity_id, amount, .. } => {
+                    if entity_id == self.pc_id {
+                        character_stats.damage_taken += amount as u64;
+                    } else if pc_turn && !self.ecs.contains_destructible(entity_id) {
+                        character_stats.damage_dealt += amount as u64;
+                    }
+                }
+                GameEvent::LevelSwitch(level_switch_action) => {
+                    if level_switch_action.entity_id == self.pc_id {
+                        character_stats.levels_entered += 1;
+                    }
+                }
+                _ if !pc_turn => {}
+                GameEvent::ProjectileFired(_) => {
+                    character_stats.bullets_fired += 1;
+                }
+                GameEvent::ProjectileHit(ProjectileCollision { collider_id, .. }) => {
+                    if self.ecs.contains_hit_points(collider_id) && !self.ecs.contains_destructible(collider_id) {
+                        character_stats.bullets_hit += 1;
+                    }
+                }
+                GameEvent::DoorOpened(_) => {
+                    character_stats.doors_opened += 1;
+                }
+                GameEvent::Died(entity_id) => {
+                    if self.ecs.contains_enemy(entity_id) {
+                        character_stats.kills += 1;
+                    }
+                }
+                GameEvent::Moved { .. } => {}
+            }
+        }
+
+        if character_stats == original {
+            None
+        } else {
+            Some(character_stats)
+        }
+    }
+
+    /// Updates the statistics of the game to account for the current
+    /// action. The action must have been summarised.
+    fn update_game_stats(&mut self) {
+        for event in self.event_bus.summary().events.iter() {
+            match *event {
+                GameEvent::Damaged { entity_id, hit_points, source, .. } => {
+                    // the player dies in a later action than the one which
+                    // takes its last hit point
+                    if entity_id == self.pc_id && !hit_points.is_positive() {
+                        let killed_by = source.and_then(|source| self.death_cause(source));
+                        self.stats.killed_by = killed_by;
+                    }
+                }
+                GameEvent::Died(entity_id) => {
+                    if entity_id != self.pc_id && self.ecs.contains_enemy(entity_id) {
+                        self.stats.enemies_killed += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn death_cause(&self, source: DamageSource) -> Option<DeathCause> {
+        match source {
+            DamageSource::Entity(entity_id) => self.ecs.tile(entity_id).map(DeathCause::Killer),
+            DamageSource::Bleeding => Some(DeathCause::Bleeding),
+            DamageSource::Explosion => Some(DeathCause::Explosion),
+            DamageSource::Impact => Some(DeathCause::Impact),
+        }
+    }
+
+    fn pc_render(&mut self, action_descriptions: &[ActionDescription], force: Option<ForceRender>) -> bool {
 
         let entity = self.ecs.entity(self.pc_id);
 
@@ -212,6 +401,7 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
                                                                  self.spatial_hash.height());
         let position = entity.position().expect("PC missing position");
         let vision_distance = entity.vision_distance().expect("PC missing vision_distance");
+        let vision_distance = entity.status_effects().map_or(vision_distance, |s| s.modify_vision_distance(vision_distance));
         let mut message_log = entity.message_log_borrow_mut().expect("PC missing message_log");
 
 
@@ -219,7 +409,7 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
 
         let mut changed = self.pc_observer.observe(position, self.spatial_hash, vision_distance, level_knowledge, action_env);
 
-        if let Some(action_description) = action_description {
+        for action_description in action_descriptions {
             if level_knowledge.can_see(action_description.coord, action_env) {
                 message_log.add(MessageType::Action(action_description.message));
                 changed = true;
@@ -239,11 +429,33 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
         changed
     }
 
+    /// Observation changes knowledge in place rather than by committing an
+    /// action, so the number of tiles explored is copied from the PC's
+    /// knowledge into its statistics by committing a separate action
+    fn update_tiles_explored(&mut self) {
+        let tiles_explored = if let Some(knowledge) = self.ecs.drawable_knowledge_borrow(self.pc_id) {
+            knowledge.num_explored() as u64
+        } else {
+            return;
+        };
+
+        if let Some(mut character_stats) = self.ecs.character_stats(self.pc_id) {
+            if character_stats.tiles_explored != tiles_explored {
+                character_stats.tiles_explored = tiles_explored;
+
+                *self.action_id += 1;
+                self.ecs_action.insert_character_stats(self.pc_id, character_stats);
+                self.summarise();
+                self.commit();
+            }
+        }
+    }
+
     fn try_commit_action(&mut self, action: ActionArgs) -> GameResult<Option<CommitResolution>> {
 
         let mut turn_time = self.ecs.turn_time(self.entity_id);
         let mut first = true;
-        let mut action_description = None;
+        let mut action_descriptions = Vec::new();
         let mut level_switch = None;
         let mut game_over_reason = None;
 
@@ -253,26 +465,46 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
 
             // render the scene if time has passed
             if action_event.time_delta != 0 {
-                if self.pc_render(action_description.as_ref(), Some(ForceRender::IgnoreShouldRender)) {
+                // descriptions are only shown once, when time first passes after their actions
+                let descriptions = mem::replace(&mut action_descriptions, Vec::new());
+                if self.pc_render(&descriptions, Some(ForceRender::IgnoreShouldRender)) {
                     // if the change in scene was visible, add a delay
-                    thread::sleep(Duration::from_millis(action_event.time_delta));
+                    self.clock.sleep(Duration::from_millis(action_event.time_delta));
                 }
             }
 
             *self.action_id += 1;
 
             // construct an action from the action args
-            action_event.event.to_action(&mut self.ecs_action, self.ecs, self.spatial_hash, self.entity_ids, self.rng.inner_mut().deref_mut());
+            action_event.event.to_action(&mut self.ecs_action, ActionEnv::new(self.ecs, *self.action_id), self.spatial_hash, self.entity_ids, self.rng.inner_mut().deref_mut());
 
             let mut action_time = 0;
             self.rule_reactions.clear();
 
+            // the action args currently being checked, which rules may replace
+            let mut current_args = action_event.event;
+
             loop {
-                match self.check_rules_wrapper() {
+                match self.check_rules_wrapper(current_args) {
                     RuleResolution::Accept => {
 
+                        self.summarise();
+                        let character_stats = self.updated_character_stats();
+
                         if self.ecs_action.contains_no_commit() {
+                            // actions which aren't committed can still be described
+                            if let Some(description) = self.ecs_action.clear_action_description() {
+                                action_descriptions.push(description);
+                            }
+
                             self.ecs_action.clear();
+
+                            // actions which aren't committed can still change statistics
+                            if let Some(character_stats) = character_stats {
+                                self.ecs_action.insert_character_stats(self.pc_id, character_stats);
+                                self.summarise();
+                                self.commit();
+                            }
                             break;
                         }
 
@@ -283,7 +515,11 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
                             }
                         }
                         action_time = self.ecs_action.action_time_ms().unwrap_or(0);
-                        action_description = self.ecs_action.clear_action_description();
+                        // keep descriptions until time passes, so every action
+                        // which happens at the same time is described
+                        if let Some(description) = self.ecs_action.clear_action_description() {
+                            action_descriptions.push(description);
+                        }
 
                         if let Some(level_switch_action) = self.ecs_action.level_switch_action() {
                             level_switch = Some(level_switch_action);
@@ -293,10 +529,16 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
                             self.turn_schedule.invalidate(ticket);
                         }
 
+                        self.update_game_stats();
+
                         if self.ecs_action.contains_player_died() {
                             game_over_reason = Some(GameOverReason::PlayerDied);
                         }
 
+                        if let Some(character_stats) = character_stats {
+                            self.ecs_action.insert_character_stats(self.pc_id, character_stats);
+                        }
+
                         self.commit();
                         break;
                     }
@@ -308,7 +550,8 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
                     }
                     RuleResolution::Consume(action_args) => {
                         // modify the current action with the new action args and retry
-                        action_args.to_action(&mut self.ecs_action, self.ecs, self.spatial_hash, self.entity_ids, self.rng.inner_mut().deref_mut());
+                        action_args.to_action(&mut self.ecs_action, ActionEnv::new(self.ecs, *self.action_id), self.spatial_hash, self.entity_ids, self.rng.inner_mut().deref_mut());
+                        current_args = action_args;
                     }
                 }
             }
@@ -319,16 +562,16 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
         }
 
         if let Some(game_over_reason) = game_over_reason {
-            self.pc_render(None, Some(ForceRender::IgnoreShouldRender));
+            self.pc_render(&action_descriptions, Some(ForceRender::IgnoreShouldRender));
             return Ok(Some(CommitResolution::GameOver(game_over_reason)));
         }
 
-        if first {
-            return Ok(None);
+        if !action_descriptions.is_empty() {
+            self.pc_render(&action_descriptions, None);
         }
 
-        if action_description.is_some() {
-            self.pc_render(action_description.as_ref(), None);
+        if first {
+            return Ok(None);
         }
 
         if let Some(level_switch) = level_switch {
@@ -357,6 +600,7 @@ impl<'game, 'level, Renderer: KnowledgeRenderer> TurnEnv<'game, 'level, Renderer
             renderer: self.renderer,
             rng: self.rng,
             language: self.language,
+            rule_trace: self.rule_trace,
         };
         Ok(behaviour_state.run(self.behaviour_ctx.graph(), input)?)
     }
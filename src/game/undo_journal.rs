@@ -0,0 +1,123 @@
+use std::collections::VecDeque;
+use rand::StdRng;
+
+use game::*;
+use ecs::*;
+use spatial_hash::*;
+
+/// Number of player turns which can be rewound
+pub const UNDO_JOURNAL_MAX_TURNS: usize = 64;
+
+/// The state of a level at the start of a player turn, along with the
+/// inverse of every action committed to the level since
+struct JournalEntry {
+    inverse_actions: Vec<EcsAction>,
+    turn_schedule: TurnSchedule,
+    rng: StdRng,
+    stats: GameStats,
+}
+
+/// Records enough information to step the current level back to the start
+/// of recent player turns. Components which are changed in place rather than
+/// by committing actions, such as knowledge, behaviour state and the message
+/// log, are not rewound. The journal only covers the current level, and is
+/// cleared when the player changes level.
+pub struct UndoJournal {
+    entries: VecDeque<JournalEntry>,
+    max_turns: usize,
+}
+
+impl UndoJournal {
+    pub fn new(max_turns: usize) -> Self {
+        UndoJournal {
+            entries: VecDeque::new(),
+            max_turns: max_turns,
+        }
+    }
+
+    pub fn disabled() -> Self {
+        Self::new(0)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.max_turns > 0
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Called at the start of each player turn
+    pub fn begin_turn(&mut self, turn_schedule: &TurnSchedule, rng: &GameRng, stats: &GameStats) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        // keep the entry for the current turn in addition to the rewindable turns
+        while self.entries.len() > self.max_turns {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(JournalEntry {
+            inverse_actions: Vec::new(),
+            turn_schedule: turn_schedule.clone(),
+            rng: rng.state(),
+            stats: *stats,
+        });
+    }
+
+    pub fn record(&mut self, inverse: EcsAction) {
+        if let Some(entry) = self.entries.back_mut() {
+            entry.inverse_actions.push(inverse);
+        }
+    }
+
+    /// Schedule tickets are inserted directly rather than by committing an
+    /// action, so their previous values must be recorded explicitly
+    pub fn record_schedule_ticket(&mut self, ecs: &EcsCtx, entity_id: EntityId) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        if let Some(ticket) = ecs.schedule_ticket(entity_id) {
+            let mut inverse = EcsAction::new();
+            inverse.insert_schedule_ticket(entity_id, ticket);
+            self.record(inverse);
+        }
+    }
+
+    /// Returns the level to the state it was in at the start of the previous
+    /// player turn. Returns false if there is no previous turn to rewind to.
+    pub fn rewind(&mut self,
+                  ecs: &mut EcsCtx,
+                  spatial_hash: &mut SpatialHashTable,
+                  turn_schedule: &mut TurnSchedule,
+                  rng: &GameRng,
+                  stats: &mut GameStats,
+                  action_id: ActionId) -> bool {
+
+        if self.entries.len() < 2 {
+            return false;
+        }
+
+        // undo anything that has happened so far this turn, then undo the previous turn
+        for _ in 0..2 {
+            let JournalEntry { inverse_actions, turn_schedule: entry_schedule, rng: entry_rng, stats: entry_stats } =
+                self.entries.pop_back().expect("Missing journal entry");
+
+            for mut inverse in inverse_actions.into_iter().rev() {
+                spatial_hash.update(ecs, &inverse, action_id);
+                ecs.commit(&mut inverse);
+            }
+
+            *turn_schedule = entry_schedule;
+            rng.set_state(entry_rng);
+            *stats = entry_stats;
+        }
+
+        // the rewound turn is now the current turn
+        self.begin_turn(turn_schedule, rng, stats);
+
+        true
+    }
+}
@@ -1,2 +1,84 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use game::save_slot;
+use game::game_file::{FileError, FileResult};
+
 pub const CONTROL: &'static str = "controls.toml";
 pub const CONFIG: &'static str = "config.toml";
+pub const SAVE: &'static str = "save";
+pub const REPLAYS: &'static str = "replays";
+pub const REPLAY_SESSION: &'static str = "replay_session";
+pub const PROFILES: &'static str = "profiles";
+pub const SLOTS: &'static str = "slots";
+pub const SLOT_INFO: &'static str = "slot.toml";
+pub const HISTORY: &'static str = "history.json";
+pub const DAILY: &'static str = "daily.json";
+
+pub const DEFAULT_PROFILE: &'static str = "default";
+
+/// Returns a path in a directory for a new file, adding a numbered suffix to
+/// the stem if a file with that name already exists
+pub fn unique_path<P: AsRef<Path>>(dir: P, stem: &str, extension: &str) -> PathBuf {
+    let dir = dir.as_ref();
+
+    let mut path = dir.join(format!("{}.{}", stem, extension));
+    let mut suffix = 2;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.{}", stem, suffix, extension));
+        suffix += 1;
+    }
+
+    path
+}
+
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Moves a save written before profiles and save slots existed, which was
+/// kept directly in the user directory, into the first empty slot of a
+/// profile. The save is left where it is if every slot is occupied.
+fn import_legacy_save(user_path: &Path, profile_path: &Path) -> FileResult<()> {
+    let legacy_save_path = user_path.join(SAVE);
+    if !legacy_save_path.is_file() {
+        return Ok(());
+    }
+
+    let empty_slot = save_slot::slots().find(|&slot| {
+        save_slot::summary(profile_path, slot) == save_slot::SaveSlotSummary::Empty
+    });
+
+    if let Some(slot) = empty_slot {
+        let slot_path = save_slot::slot_path(profile_path, slot);
+        fs::create_dir_all(&slot_path).map_err(|_| FileError::FailedToWrite)?;
+        fs::rename(legacy_save_path, slot_path.join(SAVE)).map_err(|_| FileError::FailedToWrite)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the directory of the named profile, creating it if necessary.
+/// New profiles start with a copy of the controls and configuration in the
+/// user directory, and the default profile takes over any save left in the
+/// user directory by a version of the game without profiles.
+pub fn prepare_profile<P: AsRef<Path>>(user_path: P, name: &str) -> FileResult<PathBuf> {
+    let user_path = user_path.as_ref();
+    let profile_path = user_path.join(PROFILES).join(name);
+
+    fs::create_dir_all(&profile_path).map_err(|_| FileError::FailedToWrite)?;
+
+    for file_name in &[CONTROL, CONFIG] {
+        let source = user_path.join(file_name);
+        let dest = profile_path.join(file_name);
+        if source.is_file() && !dest.exists() {
+            fs::copy(source, dest).map_err(|_| FileError::FailedToWrite)?;
+        }
+    }
+
+    if name == DEFAULT_PROFILE {
+        import_legacy_save(user_path, &profile_path)?;
+    }
+
+    Ok(profile_path)
+}
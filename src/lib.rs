@@ -0,0 +1,35 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate itertools;
+extern crate rand;
+extern crate num;
+extern crate getopts;
+extern crate toml;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+extern crate serde_json;
+extern crate bincode;
+
+#[cfg(all(unix, feature = "rustty"))]
+extern crate rustty;
+
+#[cfg(feature = "sdl2")]
+extern crate sdl2;
+
+#[macro_use]
+mod debug;
+mod frontends;
+mod ecs;
+mod math;
+pub mod game;
+mod util;
+mod direction;
+mod grid;
+mod behaviour;
+mod search;
+mod perlin;
+mod coord;
+mod colour;
+mod spatial_hash;
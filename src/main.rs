@@ -1,39 +1,9 @@
-#![allow(dead_code)]
-
-#[macro_use]
-extern crate itertools;
-extern crate rand;
-extern crate num;
+extern crate howl;
 extern crate getopts;
-extern crate toml;
-#[macro_use]
-extern crate serde_derive;
-extern crate serde;
-extern crate bincode;
-
-#[cfg(all(unix, feature = "rustty"))]
-extern crate rustty;
-
-#[cfg(feature = "sdl2")]
-extern crate sdl2;
 
 use std::env;
 
-#[macro_use]
-mod debug;
-mod frontends;
-mod ecs;
-mod math;
-mod game;
-mod util;
-mod direction;
-mod grid;
-mod behaviour;
-mod search;
-mod perlin;
-mod coord;
-mod colour;
-mod spatial_hash;
+use howl::game;
 
 fn print_usage(program: &str, opts: getopts::Options) {
     let brief = format!("Usage: {} [options]", program);
@@ -1,8 +1,11 @@
 use std::collections::HashSet;
 use std::hash::Hash;
 
+use util::serialize_unordered;
+
 #[derive(Serialize, Deserialize)]
 pub struct AnySet<T: Copy + Hash + Eq> {
+    #[serde(serialize_with = "serialize_unordered")]
     inner: HashSet<T>,
     any: Option<T>,
 }
@@ -4,6 +4,7 @@ mod best_map;
 mod schedule;
 mod any_set;
 mod dimension_constructor;
+mod stable_hash;
 
 pub use self::leaky_reserver::*;
 pub use self::bidirectional_list::*;
@@ -11,6 +12,7 @@ pub use self::best_map::*;
 pub use self::schedule::*;
 pub use self::any_set::*;
 pub use self::dimension_constructor::*;
+pub use self::stable_hash::*;
 
 #[cfg(test)]
 mod tests;
@@ -1,7 +1,9 @@
 use std::collections::{BinaryHeap, HashSet};
 use std::cmp::Ordering;
 
-#[derive(Serialize, Deserialize)]
+use util::serialize_unordered;
+
+#[derive(Clone, Serialize, Deserialize)]
 struct ScheduleEntry<T> {
     value: T,
     abs_time: u64,
@@ -69,6 +71,7 @@ pub struct ScheduleTicket {
     seq: u64,
 }
 
+#[derive(Clone)]
 pub struct Schedule<T> {
     heap: BinaryHeap<ScheduleEntry<T>>,
     invalid: HashSet<u64>,
@@ -79,6 +82,7 @@ pub struct Schedule<T> {
 #[derive(Serialize, Deserialize)]
 pub struct SerializableSchedule<T> {
     entries: Vec<ScheduleEntry<T>>,
+    #[serde(serialize_with = "serialize_unordered")]
     invalid: HashSet<u64>,
     abs_time: u64,
     seq: u64,
@@ -0,0 +1,481 @@
+use std::error;
+use std::fmt;
+use std::hash::Hasher;
+
+use serde::ser::{self, Serialize, Serializer};
+
+/// Name of the newtype struct used by `serialize_unordered` to tell the
+/// state hasher that the sequence inside it has no meaningful order
+const UNORDERED_SEQ_NAME: &'static str = "__stable_hash_unordered_seq";
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// 64-bit FNV-1a hasher. Unlike `DefaultHasher`, its output is guaranteed
+/// not to change between runs, builds or platforms.
+#[derive(Clone, Copy)]
+pub struct StableHasher(u64);
+
+impl StableHasher {
+    pub fn new() -> Self {
+        StableHasher(FNV_OFFSET_BASIS)
+    }
+
+    fn write_u64_le(&mut self, value: u64) {
+        for i in 0..8 {
+            self.write(&[(value >> (i * 8)) as u8]);
+        }
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StableHashError(String);
+
+impl fmt::Display for StableHashError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl error::Error for StableHashError {
+    fn description(&self) -> &str {
+        &self.0
+    }
+}
+
+impl ser::Error for StableHashError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        StableHashError(msg.to_string())
+    }
+}
+
+pub type StableHashResult<T> = Result<T, StableHashError>;
+
+/// Computes a hash of a value from its serialized form, which is stable
+/// across runs of the program.
+///
+/// Sequences, and fields of structs, tuples and enum variants, are combined
+/// in order. The entries of maps are combined without regard to their order,
+/// so the hash of a `HashMap` doesn't depend on its iteration order. A
+/// `HashSet` serializes as an ordinary sequence, so fields holding one should
+/// be serialized with `serialize_unordered`.
+pub fn stable_hash<T: ?Sized + Serialize>(value: &T) -> StableHashResult<u64> {
+    value.serialize(StableHashSerializer::ordered())
+}
+
+/// For use with `#[serde(serialize_with = "...")]` on fields whose elements
+/// are stored in no particular order, such as a `HashSet`. Other serializers
+/// see the sequence unchanged.
+pub fn serialize_unordered<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where T: Serialize,
+          S: Serializer,
+{
+    serializer.serialize_newtype_struct(UNORDERED_SEQ_NAME, value)
+}
+
+fn hash_u64(value: u64) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_u64_le(value);
+    hasher.finish()
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_u64_le(bytes.len() as u64);
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Scrambles a hash before it is summed with others, so that similar
+/// elements don't cancel out
+fn mix(mut value: u64) -> u64 {
+    value ^= value >> 33;
+    value = value.wrapping_mul(0xff51afd7ed558ccd);
+    value ^= value >> 33;
+    value = value.wrapping_mul(0xc4ceb9fe1a85ec53);
+    value ^ (value >> 33)
+}
+
+#[derive(Clone, Copy)]
+struct StableHashSerializer {
+    unordered_seq: bool,
+}
+
+impl StableHashSerializer {
+    fn ordered() -> Self {
+        StableHashSerializer {
+            unordered_seq: false,
+        }
+    }
+
+    fn unordered() -> Self {
+        StableHashSerializer {
+            unordered_seq: true,
+        }
+    }
+}
+
+struct OrderedHash(StableHasher);
+
+impl OrderedHash {
+    fn new() -> Self {
+        OrderedHash(StableHasher::new())
+    }
+
+    fn with_variant(variant_index: usize) -> Self {
+        let mut hash = Self::new();
+        hash.0.write_u64_le(variant_index as u64);
+        hash
+    }
+
+    fn add<T: ?Sized + Serialize>(&mut self, value: &T) -> StableHashResult<()> {
+        let hash = value.serialize(StableHashSerializer::ordered())?;
+        self.0.write_u64_le(hash);
+        Ok(())
+    }
+
+    fn finish(self) -> u64 {
+        self.0.finish()
+    }
+}
+
+/// Hashes the elements of a sequence, in order unless the sequence was
+/// marked as unordered
+enum SeqHash {
+    Ordered(OrderedHash, u64),
+    Unordered(UnorderedHash),
+}
+
+impl SeqHash {
+    fn new(unordered: bool) -> Self {
+        if unordered {
+            SeqHash::Unordered(UnorderedHash::new())
+        } else {
+            SeqHash::Ordered(OrderedHash::new(), 0)
+        }
+    }
+
+    fn add<T: ?Sized + Serialize>(&mut self, value: &T) -> StableHashResult<()> {
+        match self {
+            &mut SeqHash::Ordered(ref mut hash, ref mut len) => {
+                *len += 1;
+                hash.add(value)
+            }
+            &mut SeqHash::Unordered(ref mut hash) => {
+                let value = value.serialize(StableHashSerializer::ordered())?;
+                hash.add_hash(value);
+                Ok(())
+            }
+        }
+    }
+
+    fn finish(self) -> u64 {
+        match self {
+            SeqHash::Ordered(hash, len) => {
+                let mut hasher = StableHasher::new();
+                hasher.write_u64_le(len);
+                hasher.write_u64_le(hash.finish());
+                hasher.finish()
+            }
+            SeqHash::Unordered(hash) => hash.finish(),
+        }
+    }
+}
+
+struct UnorderedHash {
+    sum: u64,
+    len: u64,
+    key: Option<u64>,
+}
+
+impl UnorderedHash {
+    fn new() -> Self {
+        UnorderedHash {
+            sum: 0,
+            len: 0,
+            key: None,
+        }
+    }
+
+    fn add_hash(&mut self, hash: u64) {
+        self.sum = self.sum.wrapping_add(mix(hash));
+        self.len += 1;
+    }
+
+    fn finish(self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_u64_le(self.len);
+        hasher.write_u64_le(self.sum);
+        hasher.finish()
+    }
+}
+
+impl ser::Serializer for StableHashSerializer {
+    type Ok = u64;
+    type Error = StableHashError;
+    type SerializeSeq = SeqHash;
+    type SerializeTuple = OrderedHash;
+    type SerializeTupleStruct = OrderedHash;
+    type SerializeTupleVariant = OrderedHash;
+    type SerializeMap = UnorderedHash;
+    type SerializeStruct = OrderedHash;
+    type SerializeStructVariant = OrderedHash;
+
+    fn serialize_bool(self, v: bool) -> StableHashResult<u64> {
+        Ok(hash_u64(v as u64))
+    }
+
+    fn serialize_i8(self, v: i8) -> StableHashResult<u64> {
+        Ok(hash_u64(v as i64 as u64))
+    }
+
+    fn serialize_i16(self, v: i16) -> StableHashResult<u64> {
+        Ok(hash_u64(v as i64 as u64))
+    }
+
+    fn serialize_i32(self, v: i32) -> StableHashResult<u64> {
+        Ok(hash_u64(v as i64 as u64))
+    }
+
+    fn serialize_i64(self, v: i64) -> StableHashResult<u64> {
+        Ok(hash_u64(v as u64))
+    }
+
+    fn serialize_u8(self, v: u8) -> StableHashResult<u64> {
+        Ok(hash_u64(v as u64))
+    }
+
+    fn serialize_u16(self, v: u16) -> StableHashResult<u64> {
+        Ok(hash_u64(v as u64))
+    }
+
+    fn serialize_u32(self, v: u32) -> StableHashResult<u64> {
+        Ok(hash_u64(v as u64))
+    }
+
+    fn serialize_u64(self, v: u64) -> StableHashResult<u64> {
+        Ok(hash_u64(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> StableHashResult<u64> {
+        Ok(hash_u64(v.to_bits() as u64))
+    }
+
+    fn serialize_f64(self, v: f64) -> StableHashResult<u64> {
+        Ok(hash_u64(v.to_bits()))
+    }
+
+    fn serialize_char(self, v: char) -> StableHashResult<u64> {
+        Ok(hash_u64(v as u64))
+    }
+
+    fn serialize_str(self, value: &str) -> StableHashResult<u64> {
+        Ok(hash_bytes(value.as_bytes()))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> StableHashResult<u64> {
+        Ok(hash_bytes(value))
+    }
+
+    fn serialize_none(self) -> StableHashResult<u64> {
+        Ok(OrderedHash::with_variant(0).finish())
+    }
+
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> StableHashResult<u64> {
+        let mut hash = OrderedHash::with_variant(1);
+        hash.add(value)?;
+        Ok(hash.finish())
+    }
+
+    fn serialize_unit(self) -> StableHashResult<u64> {
+        Ok(OrderedHash::new().finish())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> StableHashResult<u64> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self,
+                              _name: &'static str,
+                              variant_index: usize,
+                              _variant: &'static str) -> StableHashResult<u64> {
+        Ok(OrderedHash::with_variant(variant_index).finish())
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self,
+                                                       name: &'static str,
+                                                       value: &T) -> StableHashResult<u64> {
+        if name == UNORDERED_SEQ_NAME {
+            value.serialize(StableHashSerializer::unordered())
+        } else {
+            value.serialize(StableHashSerializer::ordered())
+        }
+    }
+
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(self,
+                                                        _name: &'static str,
+                                                        variant_index: usize,
+                                                        _variant: &'static str,
+                                                        value: &T) -> StableHashResult<u64> {
+        let mut hash = OrderedHash::with_variant(variant_index);
+        hash.add(value)?;
+        Ok(hash.finish())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> StableHashResult<SeqHash> {
+        Ok(SeqHash::new(self.unordered_seq))
+    }
+
+    fn serialize_seq_fixed_size(self, _size: usize) -> StableHashResult<SeqHash> {
+        Ok(SeqHash::new(self.unordered_seq))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> StableHashResult<OrderedHash> {
+        Ok(OrderedHash::new())
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> StableHashResult<OrderedHash> {
+        Ok(OrderedHash::new())
+    }
+
+    fn serialize_tuple_variant(self,
+                               _name: &'static str,
+                               variant_index: usize,
+                               _variant: &'static str,
+                               _len: usize) -> StableHashResult<OrderedHash> {
+        Ok(OrderedHash::with_variant(variant_index))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> StableHashResult<UnorderedHash> {
+        Ok(UnorderedHash::new())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> StableHashResult<OrderedHash> {
+        Ok(OrderedHash::new())
+    }
+
+    fn serialize_struct_variant(self,
+                                _name: &'static str,
+                                variant_index: usize,
+                                _variant: &'static str,
+                                _len: usize) -> StableHashResult<OrderedHash> {
+        Ok(OrderedHash::with_variant(variant_index))
+    }
+}
+
+impl ser::SerializeSeq for SeqHash {
+    type Ok = u64;
+    type Error = StableHashError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> StableHashResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> StableHashResult<u64> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeMap for UnorderedHash {
+    type Ok = u64;
+    type Error = StableHashError;
+
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> StableHashResult<()> {
+        self.key = Some(key.serialize(StableHashSerializer::ordered())?);
+        Ok(())
+    }
+
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> StableHashResult<()> {
+        let key = self.key.take().expect("Map value serialized without key");
+        let value = value.serialize(StableHashSerializer::ordered())?;
+
+        let mut hasher = StableHasher::new();
+        hasher.write_u64_le(key);
+        hasher.write_u64_le(value);
+        self.add_hash(hasher.finish());
+
+        Ok(())
+    }
+
+    fn end(self) -> StableHashResult<u64> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for OrderedHash {
+    type Ok = u64;
+    type Error = StableHashError;
+
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> StableHashResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> StableHashResult<u64> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for OrderedHash {
+    type Ok = u64;
+    type Error = StableHashError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> StableHashResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> StableHashResult<u64> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleVariant for OrderedHash {
+    type Ok = u64;
+    type Error = StableHashError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> StableHashResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> StableHashResult<u64> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStruct for OrderedHash {
+    type Ok = u64;
+    type Error = StableHashError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, _key: &'static str, value: &T) -> StableHashResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> StableHashResult<u64> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeStructVariant for OrderedHash {
+    type Ok = u64;
+    type Error = StableHashError;
+
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, _key: &'static str, value: &T) -> StableHashResult<()> {
+        self.add(value)
+    }
+
+    fn end(self) -> StableHashResult<u64> {
+        Ok(self.finish())
+    }
+}
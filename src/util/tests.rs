@@ -13,3 +13,65 @@ fn bidirectional_list_get_mut_with_default() {
     assert_eq!(l.get_checked(-1), &1);
     assert_eq!(l.get_checked(1), &2);
 }
+
+#[test]
+fn stable_hash_ignores_map_order() {
+    use std::collections::HashMap;
+
+    let mut a = HashMap::new();
+    let mut b = HashMap::new();
+
+    for i in 0..100 {
+        a.insert(i, i * 2);
+    }
+    for i in (0..100).rev() {
+        b.insert(i, i * 2);
+    }
+
+    assert_eq!(stable_hash(&a).unwrap(), stable_hash(&b).unwrap());
+
+    b.insert(0, 1);
+    assert!(stable_hash(&a).unwrap() != stable_hash(&b).unwrap());
+}
+
+#[test]
+fn stable_hash_respects_field_order() {
+    assert!(stable_hash(&(1, 2)).unwrap() != stable_hash(&(2, 1)).unwrap());
+    assert!(stable_hash(&Some(0)).unwrap() != stable_hash(&None::<u32>).unwrap());
+}
+
+#[test]
+fn stable_hash_respects_sequence_order() {
+    let a = vec![1, 2, 3];
+    let mut b = a.clone();
+    b.swap(0, 2);
+
+    assert!(stable_hash(&a).unwrap() != stable_hash(&b).unwrap());
+}
+
+#[test]
+fn stable_hash_ignores_unordered_set_order() {
+    use std::collections::HashSet;
+
+    #[derive(Serialize)]
+    struct Wrapper {
+        #[serde(serialize_with = "serialize_unordered")]
+        set: HashSet<u32>,
+    }
+
+    // sets with different capacities are likely to iterate in different orders
+    let mut a = Wrapper { set: HashSet::new() };
+    let mut b = Wrapper { set: HashSet::with_capacity(1000) };
+
+    for i in 0..100 {
+        a.set.insert(i);
+    }
+    for i in (0..100).rev() {
+        b.set.insert(i);
+    }
+
+    assert_eq!(stable_hash(&a).unwrap(), stable_hash(&b).unwrap());
+
+    b.set.remove(&0);
+    assert!(stable_hash(&a).unwrap() != stable_hash(&b).unwrap());
+}